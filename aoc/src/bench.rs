@@ -0,0 +1,348 @@
+use crate::days;
+use crate::parse_day;
+use common::InputSource;
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchArgs {
+    days: Vec<u32>,
+    runs: usize,
+    format: Format,
+    baseline: Option<String>,
+    threshold: f64,
+    input: InputSource,
+}
+
+/**
+ * Timing summary for one stage (parse, part1 or part2) of one day.
+ */
+#[derive(Debug, PartialEq)]
+struct Record {
+    day: u32,
+    stage: &'static str,
+    runs: usize,
+    min: Duration,
+    median: Duration,
+    max: Duration,
+    baseline: Option<Duration>,
+}
+
+impl Record {
+    /**
+     * Relative change of the median against the baseline, in percent.
+     */
+    fn change(&self) -> Option<f64> {
+        let baseline = self.baseline?.as_secs_f64();
+        (baseline > 0.0).then(|| (self.median.as_secs_f64() / baseline - 1.0) * 100.0)
+    }
+
+    fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+pub fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut args = args.iter();
+    let days = parse_day(args.next().ok_or(crate::USAGE)?)?;
+    let mut bench = BenchArgs {
+        days,
+        runs: 10,
+        format: Format::Text,
+        baseline: None,
+        threshold: 10.0,
+        input: InputSource::DayDirectory,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} requires a value"));
+        match arg.as_str() {
+            "--runs" => {
+                let value = value()?;
+                bench.runs = match value.parse() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(format!("invalid run count '{value}'")),
+                };
+            }
+            "--format" => {
+                let value = value()?;
+                bench.format = match value.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    _ => {
+                        return Err(format!(
+                            "invalid format '{value}', expected text, json or csv"
+                        ))
+                    }
+                };
+            }
+            "--baseline" => bench.baseline = Some(value()?.clone()),
+            "--threshold" => {
+                let value = value()?;
+                bench.threshold = match value.parse() {
+                    Ok(threshold) if threshold >= 0.0 => threshold,
+                    _ => return Err(format!("invalid threshold '{value}'")),
+                };
+            }
+            "--input" | "--input-dir" => {
+                bench.input =
+                    InputSource::from_option(arg, value()?).expect("matched an input option")
+            }
+            _ => return Err(format!("unexpected argument '{arg}'\n{}", crate::USAGE)),
+        }
+    }
+    if bench.input.is_single() && bench.days.len() > 1 {
+        return Err("--input can only be used when benchmarking a single day".to_string());
+    }
+    Ok(bench)
+}
+
+/**
+ * Minimum, median and maximum of a non-empty set of timings.
+ */
+fn stats(timings: &[Duration]) -> (Duration, Duration, Duration) {
+    let mut sorted = timings.to_vec();
+    sorted.sort();
+    let n = sorted.len();
+    let median = if n.is_multiple_of(2) {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+    } else {
+        sorted[n / 2]
+    };
+    (sorted[0], median, sorted[n - 1])
+}
+
+/**
+ * Read the median times of a baseline saved with `--format csv`, keyed by day
+ * and stage.
+ */
+fn parse_baseline(csv: &str) -> Result<HashMap<(u32, String), Duration>, String> {
+    let mut lines = csv.lines();
+    let header: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|&column| column == name)
+            .ok_or(format!("baseline is missing the '{name}' column"))
+    };
+    let (day, stage, median) = (column("day")?, column("stage")?, column("median_ns")?);
+    let mut baseline = HashMap::new();
+    for (number, line) in (2..).zip(lines) {
+        let fields: Vec<&str> = line.split(',').collect();
+        let field = |index: usize| {
+            fields
+                .get(index)
+                .copied()
+                .ok_or(format!("baseline line {number}: missing field"))
+        };
+        let invalid = |_| format!("baseline line {number}: invalid number");
+        let key = (
+            field(day)?.parse().map_err(invalid)?,
+            field(stage)?.to_string(),
+        );
+        let nanos: u64 = field(median)?.parse().map_err(invalid)?;
+        baseline.insert(key, Duration::from_nanos(nanos));
+    }
+    Ok(baseline)
+}
+
+fn render_text(records: &[Record], threshold: f64) -> String {
+    let mut output = String::new();
+    for record in records {
+        output += &format!(
+            "Day {:>2} {:<5}  min {:>10.3?}  median {:>10.3?}  max {:>10.3?}",
+            record.day, record.stage, record.min, record.median, record.max
+        );
+        if let Some(change) = record.change() {
+            output += &format!("  {change:+.1}% vs baseline");
+            if record.is_regression(threshold) {
+                output += "  REGRESSION";
+            }
+        }
+        output += "\n";
+    }
+    output
+}
+
+fn render_csv(records: &[Record], threshold: f64) -> String {
+    let mut output =
+        String::from("day,stage,runs,min_ns,median_ns,max_ns,baseline_ns,regression\n");
+    for record in records {
+        let baseline = record
+            .baseline
+            .map_or(String::new(), |baseline| baseline.as_nanos().to_string());
+        output += &format!(
+            "{},{},{},{},{},{},{},{}\n",
+            record.day,
+            record.stage,
+            record.runs,
+            record.min.as_nanos(),
+            record.median.as_nanos(),
+            record.max.as_nanos(),
+            baseline,
+            record.is_regression(threshold)
+        );
+    }
+    output
+}
+
+fn render_json(records: &[Record], threshold: f64) -> String {
+    let objects: Vec<String> = records
+        .iter()
+        .map(|record| {
+            let baseline = record
+                .baseline
+                .map_or("null".to_string(), |baseline| baseline.as_nanos().to_string());
+            format!(
+                "  {{\"day\": {}, \"stage\": \"{}\", \"runs\": {}, \"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}, \"baseline_ns\": {}, \"regression\": {}}}",
+                record.day,
+                record.stage,
+                record.runs,
+                record.min.as_nanos(),
+                record.median.as_nanos(),
+                record.max.as_nanos(),
+                baseline,
+                record.is_regression(threshold)
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+pub fn bench(args: BenchArgs) -> Result<(), String> {
+    let baseline = match &args.baseline {
+        Some(path) => {
+            let csv = fs::read_to_string(path)
+                .map_err(|err| format!("failed to read baseline {path}: {err}"))?;
+            parse_baseline(&csv).map_err(|err| format!("{path}: {err}"))?
+        }
+        None => HashMap::new(),
+    };
+    let mut records = Vec::new();
+    for day in args.days {
+        let input = args
+            .input
+            .read(day)
+            .map_err(|err| format!("Day {day}: {err}"))?;
+        let timings = days::bench(day, &input, args.runs)
+            .ok_or(format!("Day {day} does not exist"))?
+            .map_err(|err| format!("Day {day}: {}: {err}", args.input.name(day)))?;
+        for (stage, timings) in [
+            ("parse", &timings.parse),
+            ("part1", &timings.part1),
+            ("part2", &timings.part2),
+        ] {
+            let (min, median, max) = stats(timings);
+            records.push(Record {
+                day,
+                stage,
+                runs: args.runs,
+                min,
+                median,
+                max,
+                baseline: baseline.get(&(day, stage.to_string())).copied(),
+            });
+        }
+    }
+    let output = match args.format {
+        Format::Text => render_text(&records, args.threshold),
+        Format::Json => render_json(&records, args.threshold),
+        Format::Csv => render_csv(&records, args.threshold),
+    };
+    print!("{output}");
+    let regressions = records
+        .iter()
+        .filter(|record| record.is_regression(args.threshold))
+        .count();
+    match regressions {
+        0 => Ok(()),
+        n => Err(format!(
+            "{n} stage(s) regressed by more than {}% against the baseline",
+            args.threshold
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    fn record(stage: &'static str, median: u64, baseline: Option<u64>) -> Record {
+        Record {
+            day: 12,
+            stage,
+            runs: 3,
+            min: Duration::from_nanos(median / 2),
+            median: Duration::from_nanos(median),
+            max: Duration::from_nanos(median * 2),
+            baseline: baseline.map(Duration::from_nanos),
+        }
+    }
+
+    #[test]
+    fn test_parse_bench_args() {
+        assert_eq!(
+            parse_bench_args(&args(
+                "12 --runs 5 --format csv --baseline base.csv --threshold 25"
+            )),
+            Ok(BenchArgs {
+                days: vec![12],
+                runs: 5,
+                format: Format::Csv,
+                baseline: Some("base.csv".to_string()),
+                threshold: 25.0,
+                input: InputSource::DayDirectory,
+            })
+        );
+        assert_eq!(parse_bench_args(&args("all")).unwrap().runs, 10);
+        assert!(parse_bench_args(&args("1 --runs 0")).is_err());
+        assert!(parse_bench_args(&args("1 --format xml")).is_err());
+        assert!(parse_bench_args(&args("1 --threshold")).is_err());
+        assert!(parse_bench_args(&args("all --input day01.txt")).is_err());
+    }
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        assert_eq!(stats(&[ms(3), ms(1), ms(2)]), (ms(1), ms(2), ms(3)));
+        assert_eq!(stats(&[ms(4), ms(1), ms(2), ms(8)]), (ms(1), ms(3), ms(8)));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let records = [
+            record("parse", 100, None),
+            record("part1", 2000, Some(1000)),
+            record("part2", 1050, Some(1000)),
+        ];
+        let csv = render_csv(&records, 10.0);
+        assert_eq!(
+            csv,
+            "day,stage,runs,min_ns,median_ns,max_ns,baseline_ns,regression
+12,parse,3,50,100,200,,false
+12,part1,3,1000,2000,4000,1000,true
+12,part2,3,525,1050,2100,1000,false
+"
+        );
+        let baseline = parse_baseline(&csv).unwrap();
+        assert_eq!(
+            baseline.get(&(12, "part1".to_string())),
+            Some(&Duration::from_nanos(2000))
+        );
+        assert_eq!(baseline.len(), 3);
+        assert!(parse_baseline("day,stage\n1,parse").is_err());
+        assert!(parse_baseline("day,stage,median_ns\n1,parse,fast").is_err());
+    }
+}
@@ -0,0 +1,246 @@
+use crate::days;
+use crate::parse_day;
+use common::InputSource;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+
+/**
+ * Recorded answers for one day, as read from `dayNN/answers.toml`.
+ */
+#[derive(Debug, Default, PartialEq)]
+struct Expected {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Expected {
+    fn part(&self, part: u32) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {expected}, got {actual})")
+            }
+            Status::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+fn answers_path(day: u32) -> String {
+    format!("day{day:02}/answers.toml")
+}
+
+/**
+ * Parse the `part1 = "..."` and `part2 = "..."` entries of an answers file.
+ * Values may be quoted strings or bare integers; `#` starts a comment.
+ */
+fn parse_expected(text: &str) -> Result<Expected, String> {
+    let mut expected = Expected::default();
+    for (number, line) in (1..).zip(text.lines()) {
+        let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(format!("line {number}: expected <key> = <value>"))?;
+        let value = value.trim();
+        let value = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+            Some(value) => value,
+            None if value.parse::<i128>().is_ok() => value,
+            None => return Err(format!("line {number}: invalid value {value}")),
+        };
+        let slot = match key.trim() {
+            "part1" => &mut expected.part1,
+            "part2" => &mut expected.part2,
+            key => return Err(format!("line {number}: unknown key {key}")),
+        };
+        if slot.replace(value.to_string()).is_some() {
+            return Err(format!("line {number}: duplicate key {}", key.trim()));
+        }
+    }
+    Ok(expected)
+}
+
+fn compare(expected: &Expected, answers: Vec<(u32, String)>) -> Vec<(u32, Status)> {
+    answers
+        .into_iter()
+        .map(|(part, actual)| {
+            let status = match expected.part(part) {
+                None => Status::Missing,
+                Some(expected) if expected == actual => Status::Pass,
+                Some(expected) => Status::Fail {
+                    expected: expected.to_string(),
+                    actual,
+                },
+            };
+            (part, status)
+        })
+        .collect()
+}
+
+/**
+ * Solve both parts of one day and compare them with its recorded answers.
+ */
+fn check_day(day: u32, source: &InputSource) -> Result<Vec<(u32, Status)>, String> {
+    let answers_path = answers_path(day);
+    let expected = match fs::read_to_string(&answers_path) {
+        Ok(text) => parse_expected(&text).map_err(|err| format!("{answers_path}: {err}"))?,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return Ok(vec![(1, Status::Missing), (2, Status::Missing)]);
+        }
+        Err(err) => return Err(format!("failed to read {answers_path}: {err}")),
+    };
+    let input = source.read(day).map_err(|err| err.to_string())?;
+    let solved = days::solve(day, &[1, 2], &input)
+        .ok_or(format!("Day {day} does not exist"))?
+        .map_err(|err| format!("{}: {err}", source.name(day)))?;
+    let answers = solved
+        .answers
+        .into_iter()
+        .map(|answer| (answer.part, answer.text))
+        .collect();
+    Ok(compare(&expected, answers))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CheckArgs {
+    days: Vec<u32>,
+    input: InputSource,
+}
+
+pub fn parse_check_args(args: &[String]) -> Result<CheckArgs, String> {
+    let mut args = args.iter();
+    let days = parse_day(args.next().ok_or(crate::USAGE)?)?;
+    let mut input = InputSource::DayDirectory;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "--input-dir" => {
+                let value = args.next().ok_or(format!("{arg} requires a value"))?;
+                input = InputSource::from_option(arg, value).expect("matched an input option");
+            }
+            _ => return Err(format!("unexpected argument '{arg}'\n{}", crate::USAGE)),
+        }
+    }
+    if input.is_single() && days.len() > 1 {
+        return Err("--input can only be used when checking a single day".to_string());
+    }
+    Ok(CheckArgs { days, input })
+}
+
+pub fn check(args: CheckArgs) -> Result<(), String> {
+    let mut failures = 0;
+    for day in args.days {
+        match check_day(day, &args.input) {
+            Ok(statuses) => {
+                for (part, status) in statuses {
+                    println!("Day {day} Part {part}: {status}");
+                    if matches!(status, Status::Fail { .. }) {
+                        failures += 1;
+                    }
+                }
+            }
+            Err(err) => {
+                println!("Day {day}: ERROR {err}");
+                failures += 1;
+            }
+        }
+    }
+    match failures {
+        0 => Ok(()),
+        n => Err(format!("{n} check(s) failed")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expected() {
+        assert_eq!(
+            parse_expected("# Day 8\npart1 = 16409\npart2 = \"11795205644011\" # locked in\n"),
+            Ok(Expected {
+                part1: Some("16409".to_string()),
+                part2: Some("11795205644011".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_expected("part2 = \"-\""),
+            Ok(Expected {
+                part1: None,
+                part2: Some("-".to_string()),
+            })
+        );
+        assert!(parse_expected("part3 = 1").is_err());
+        assert!(parse_expected("part1 = abc").is_err());
+        assert!(parse_expected("part1 = 1\npart1 = 2").is_err());
+    }
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_check_args() {
+        assert_eq!(
+            parse_check_args(&args("8 --input path")),
+            Ok(CheckArgs {
+                days: vec![8],
+                input: InputSource::File("path".into()),
+            })
+        );
+        assert_eq!(
+            parse_check_args(&args("all --input-dir inputs")),
+            Ok(CheckArgs {
+                days: (1..=25).collect(),
+                input: InputSource::Directory("inputs".into()),
+            })
+        );
+        assert_eq!(
+            parse_check_args(&args("3")).unwrap().input,
+            InputSource::DayDirectory
+        );
+        assert!(parse_check_args(&args("all --input -")).is_err());
+        assert!(parse_check_args(&args("3 --part 1")).is_err());
+        assert!(parse_check_args(&args("3 --input")).is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let expected = parse_expected("part1 = 30").unwrap();
+        assert_eq!(
+            compare(
+                &expected,
+                vec![(1, "30".to_string()), (2, "145".to_string())]
+            ),
+            vec![(1, Status::Pass), (2, Status::Missing)]
+        );
+        assert_eq!(
+            compare(&expected, vec![(1, "31".to_string())]),
+            vec![(
+                1,
+                Status::Fail {
+                    expected: "30".to_string(),
+                    actual: "31".to_string(),
+                }
+            )]
+        );
+    }
+}
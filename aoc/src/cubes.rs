@@ -0,0 +1,132 @@
+use common::InputSource;
+use common::Solution;
+use day02::Cubes;
+use day02::Day02;
+use day02::Games;
+
+#[derive(Debug, PartialEq)]
+enum Query {
+    Possible(String),
+    Minimal,
+    Stats,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CubesArgs {
+    query: Query,
+    input: InputSource,
+}
+
+pub fn parse_cubes_args(args: &[String]) -> Result<CubesArgs, String> {
+    let mut args = args.iter();
+    let query = match args.next().map(|arg| arg.as_str()) {
+        Some("possible") => Query::Possible(args.next().ok_or("possible requires a bag")?.clone()),
+        Some("minimal") => Query::Minimal,
+        Some("stats") => Query::Stats,
+        _ => return Err(crate::USAGE.to_string()),
+    };
+    let mut input = InputSource::DayDirectory;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "--input-dir" => {
+                let value = args.next().ok_or(format!("{arg} requires a value"))?;
+                input = InputSource::from_option(arg, value).expect("matched an input option");
+            }
+            _ => return Err(format!("unexpected argument '{arg}'\n{}", crate::USAGE)),
+        }
+    }
+    Ok(CubesArgs { query, input })
+}
+
+fn answer(query: &Query, games: &Games) -> Result<String, String> {
+    let mut output = String::new();
+    match query {
+        Query::Possible(bag) => {
+            let bag = Cubes::parse(bag).map_err(|err| format!("bag: {err}"))?;
+            let possible: Vec<u32> = games
+                .iter()
+                .filter(|game| game.is_possible(&bag))
+                .map(|game| game.id)
+                .collect();
+            for id in &possible {
+                output += &format!("Game {id}\n");
+            }
+            output += &format!(
+                "{} of {} games possible with {bag}, ids sum to {}\n",
+                possible.len(),
+                games.len(),
+                possible.iter().sum::<u32>()
+            );
+        }
+        Query::Minimal => {
+            for game in games.iter() {
+                let bag = game.minimal_bag();
+                let colours: Vec<&str> = bag.iter().map(|(colour, _)| colour).collect();
+                output += &format!("Game {}: {bag} (power {})\n", game.id, bag.power(&colours));
+            }
+        }
+        Query::Stats => output += &day02::stats(games).to_string(),
+    }
+    Ok(output)
+}
+
+/**
+ * Answer a query about the cube games of day 2.
+ */
+pub fn cubes(args: CubesArgs) -> Result<(), String> {
+    let day = Day02::DAY;
+    let input = args.input.read(day).map_err(|err| err.to_string())?;
+    let games = Day02::parse(&input).map_err(|err| format!("{}: {err}", args.input.name(day)))?;
+    print!("{}", answer(&args.query, &games)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_cubes_args() {
+        assert_eq!(
+            parse_cubes_args(&args(&["possible", "12 red, 13 green", "--input", "-"])),
+            Ok(CubesArgs {
+                query: Query::Possible("12 red, 13 green".to_string()),
+                input: InputSource::Stdin,
+            })
+        );
+        assert_eq!(
+            parse_cubes_args(&args(&["stats"])),
+            Ok(CubesArgs {
+                query: Query::Stats,
+                input: InputSource::DayDirectory,
+            })
+        );
+        assert!(parse_cubes_args(&args(&["possible"])).is_err());
+        assert!(parse_cubes_args(&args(&["maximal"])).is_err());
+        assert!(parse_cubes_args(&args(&["minimal", "--part", "1"])).is_err());
+    }
+
+    #[test]
+    fn test_answer() {
+        let games = Day02::parse(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        )
+        .unwrap();
+        let possible = Query::Possible("12 red, 13 green, 14 blue".to_string());
+        assert_eq!(
+            answer(&possible, &games).unwrap(),
+            "Game 1\n1 of 2 games possible with 12 red, 13 green, 14 blue, ids sum to 1\n"
+        );
+        assert_eq!(
+            answer(&Query::Minimal, &games).unwrap(),
+            "Game 1: 6 blue, 4 red, 2 green (power 48)\nGame 3: 13 green, 6 blue, 20 red (power 1560)\n"
+        );
+        let invalid = Query::Possible("999 red".to_string());
+        assert!(answer(&invalid, &games).is_err());
+    }
+}
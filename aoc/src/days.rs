@@ -0,0 +1,155 @@
+use common::Answer;
+use common::ParseError;
+use common::Solution;
+use std::hint::black_box;
+use std::time::Duration;
+use std::time::Instant;
+
+pub const DAYS: u32 = 25;
+
+/**
+ * One solved part: its answer as displayed and as a JSON value, and how long
+ * solving it took.
+ */
+#[derive(Debug)]
+pub struct PartAnswer {
+    pub part: u32,
+    pub text: String,
+    pub json: String,
+    pub time: Duration,
+}
+
+/**
+ * The requested answers of one day and the time spent parsing its input.
+ */
+#[derive(Debug)]
+pub struct Solved {
+    pub parse_time: Duration,
+    pub answers: Vec<PartAnswer>,
+}
+
+/**
+ * Wall times of every benchmark run, one entry per run for each stage.
+ */
+#[derive(Debug, Default)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+/**
+ * The entry points of one day, monomorphised for its Solution.
+ */
+struct Day {
+    solve: fn(&str, &[u32]) -> Result<Solved, ParseError>,
+    bench: fn(&str, usize) -> Result<Timings, ParseError>,
+}
+
+fn render(part: u32, answer: impl Answer, start: Instant) -> PartAnswer {
+    let time = start.elapsed();
+    PartAnswer {
+        part,
+        text: answer.to_string(),
+        json: answer.to_json(),
+        time,
+    }
+}
+
+/**
+ * Parse the input once and solve the requested parts, timing each stage.
+ */
+fn solve_with<S: Solution>(input: &str, parts: &[u32]) -> Result<Solved, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            match part {
+                1 => render(part, S::part1(&input), start),
+                _ => render(part, S::part2(&input), start),
+            }
+        })
+        .collect();
+    Ok(Solved {
+        parse_time,
+        answers,
+    })
+}
+
+/**
+ * Parse the input and solve both parts `runs` times, timing each stage
+ * separately.
+ */
+fn bench_with<S: Solution>(input: &str, runs: usize) -> Result<Timings, ParseError> {
+    let mut timings = Timings::default();
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = S::parse(black_box(input))?;
+        timings.parse.push(start.elapsed());
+        let start = Instant::now();
+        black_box(S::part1(&parsed));
+        timings.part1.push(start.elapsed());
+        let start = Instant::now();
+        black_box(S::part2(&parsed));
+        timings.part2.push(start.elapsed());
+    }
+    Ok(timings)
+}
+
+fn entry<S: Solution>() -> Day {
+    Day {
+        solve: solve_with::<S>,
+        bench: bench_with::<S>,
+    }
+}
+
+fn lookup(day: u32) -> Option<Day> {
+    let day = match day {
+        1 => entry::<day01::Day01>(),
+        2 => entry::<day02::Day02>(),
+        3 => entry::<day03::Day03>(),
+        4 => entry::<day04::Day04>(),
+        5 => entry::<day05::Day05>(),
+        6 => entry::<day06::Day06>(),
+        7 => entry::<day07::Day07>(),
+        8 => entry::<day08::Day08>(),
+        9 => entry::<day09::Day09>(),
+        10 => entry::<day10::Day10>(),
+        11 => entry::<day11::Day11>(),
+        12 => entry::<day12::Day12>(),
+        13 => entry::<day13::Day13>(),
+        14 => entry::<day14::Day14>(),
+        15 => entry::<day15::Day15>(),
+        16 => entry::<day16::Day16>(),
+        17 => entry::<day17::Day17>(),
+        18 => entry::<day18::Day18>(),
+        19 => entry::<day19::Day19>(),
+        20 => entry::<day20::Day20>(),
+        21 => entry::<day21::Day21>(),
+        22 => entry::<day22::Day22>(),
+        23 => entry::<day23::Day23>(),
+        24 => entry::<day24::Day24>(),
+        25 => entry::<day25::Day25>(),
+        _ => return None,
+    };
+    Some(day)
+}
+
+/**
+ * Solve the given parts of a day's puzzle. Returns None when the day does not
+ * exist.
+ */
+pub fn solve(day: u32, parts: &[u32], input: &str) -> Option<Result<Solved, ParseError>> {
+    lookup(day).map(|day| (day.solve)(input, parts))
+}
+
+/**
+ * Time `runs` rounds of parsing and solving a day's puzzle. Returns None when
+ * the day does not exist.
+ */
+pub fn bench(day: u32, input: &str, runs: usize) -> Option<Result<Timings, ParseError>> {
+    lookup(day).map(|day| (day.bench)(input, runs))
+}
@@ -0,0 +1,234 @@
+mod bench;
+mod check;
+mod cubes;
+mod days;
+
+use common::InputSource;
+use std::env;
+use std::process;
+
+const USAGE: &str = "Usage: aoc run <day|all> [--part <1|2>] [--format <text|json>] [--input <file|->] [--input-dir <dir>]
+       aoc check <day|all> [--input <file|->] [--input-dir <dir>]
+       aoc cubes <possible <bag>|minimal|stats> [--input <file|->] [--input-dir <dir>]
+       aoc bench <day|all> [--runs <n>] [--format <text|json|csv>] [--baseline <csv file>] [--threshold <percent>]
+                 [--input <file|->] [--input-dir <dir>]";
+
+#[derive(Debug, PartialEq)]
+struct RunArgs {
+    days: Vec<u32>,
+    part: Option<u32>,
+    input: InputSource,
+    json: bool,
+}
+
+fn parse_day(arg: &str) -> Result<Vec<u32>, String> {
+    if arg == "all" {
+        return Ok((1..=days::DAYS).collect());
+    }
+    match arg.parse() {
+        Ok(day) if (1..=days::DAYS).contains(&day) => Ok(vec![day]),
+        _ => Err(format!(
+            "invalid day '{arg}', expected 1-{} or 'all'",
+            days::DAYS
+        )),
+    }
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut args = args.iter();
+    let days = parse_day(args.next().ok_or(USAGE)?)?;
+    let mut part = None;
+    let mut input = InputSource::DayDirectory;
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = args.next().ok_or("--part requires a value")?;
+                match value.as_str() {
+                    "1" => part = Some(1),
+                    "2" => part = Some(2),
+                    _ => return Err(format!("invalid part '{value}', expected 1 or 2")),
+                }
+            }
+            "--format" => {
+                let value = args.next().ok_or("--format requires a value")?;
+                match value.as_str() {
+                    "text" => json = false,
+                    "json" => json = true,
+                    _ => return Err(format!("invalid format '{value}', expected text or json")),
+                }
+            }
+            "--input" | "--input-dir" => {
+                let value = args.next().ok_or(format!("{arg} requires a value"))?;
+                input = InputSource::from_option(arg, value).expect("matched an input option");
+            }
+            _ => return Err(format!("unexpected argument '{arg}'\n{USAGE}")),
+        }
+    }
+    if input.is_single() && days.len() > 1 {
+        return Err("--input can only be used when running a single day".to_string());
+    }
+    Ok(RunArgs {
+        days,
+        part,
+        input,
+        json,
+    })
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    for day in args.days {
+        let input = args
+            .input
+            .read(day)
+            .map_err(|err| format!("Day {day}: {err}"))?;
+        let solved = days::solve(day, &parts, &input)
+            .ok_or(format!("Day {day} does not exist"))?
+            .map_err(|err| format!("Day {day}: {}: {err}", args.input.name(day)))?;
+        if args.json {
+            println!("{}", render_json(day, &solved));
+            continue;
+        }
+        for answer in solved.answers {
+            println!("Day {day} Part {}: {}", answer.part, answer.text);
+        }
+    }
+    Ok(())
+}
+
+/**
+ * One line of JSON with a day's answers and the nanoseconds spent on each
+ * stage.
+ */
+fn render_json(day: u32, solved: &days::Solved) -> String {
+    let mut fields = vec![format!("\"day\":{day}")];
+    let mut timings = vec![format!("\"parse\":{}", solved.parse_time.as_nanos())];
+    for answer in &solved.answers {
+        fields.push(format!("\"part{}\":{}", answer.part, answer.json));
+        timings.push(format!(
+            "\"part{}\":{}",
+            answer.part,
+            answer.time.as_nanos()
+        ));
+    }
+    fields.push(format!("\"timings_ns\":{{{}}}", timings.join(",")));
+    format!("{{{}}}", fields.join(","))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(|arg| arg.as_str()) {
+        Some("run") => parse_run_args(&args[1..]).and_then(run),
+        Some("check") => check::parse_check_args(&args[1..]).and_then(check::check),
+        Some("cubes") => cubes::parse_cubes_args(&args[1..]).and_then(cubes::cubes),
+        Some("bench") => bench::parse_bench_args(&args[1..]).and_then(bench::bench),
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(message) = result {
+        eprintln!("{message}");
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_run_args() {
+        assert_eq!(
+            parse_run_args(&args("17 --part 2 --input path")),
+            Ok(RunArgs {
+                days: vec![17],
+                part: Some(2),
+                input: InputSource::File("path".into()),
+                json: false,
+            })
+        );
+        assert_eq!(
+            parse_run_args(&args("all")),
+            Ok(RunArgs {
+                days: (1..=25).collect(),
+                part: None,
+                input: InputSource::DayDirectory,
+                json: false,
+            })
+        );
+        assert_eq!(
+            parse_run_args(&args("all --input-dir inputs"))
+                .unwrap()
+                .input,
+            InputSource::Directory("inputs".into())
+        );
+        assert_eq!(
+            parse_run_args(&args("7 --input -")).unwrap().input,
+            InputSource::Stdin
+        );
+        assert!(parse_run_args(&args("26")).is_err());
+        assert!(parse_run_args(&args("3 --part 3")).is_err());
+        assert!(parse_run_args(&args("all --input path")).is_err());
+        assert!(parse_run_args(&args("all --input -")).is_err());
+        assert!(parse_run_args(&args("3 --format json")).unwrap().json);
+        assert!(parse_run_args(&args("3 --format csv")).is_err());
+    }
+
+    fn answers(day: u32, parts: &[u32], input: &str) -> Vec<(u32, String, String)> {
+        days::solve(day, parts, input)
+            .unwrap()
+            .unwrap()
+            .answers
+            .into_iter()
+            .map(|answer| (answer.part, answer.text, answer.json))
+            .collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            answers(15, &[1], "rn=1"),
+            vec![(1, "30".to_string(), "30".to_string())]
+        );
+        assert_eq!(
+            answers(25, &[2], "a: b"),
+            vec![(2, "-".to_string(), "null".to_string())]
+        );
+        assert_eq!(
+            days::solve(2, &[1], "Game 1 3 blue")
+                .unwrap()
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 6: expected \": \" at \"1 3 blue\""
+        );
+        assert!(days::solve(26, &[1], "").is_none());
+    }
+
+    #[test]
+    fn test_render_json() {
+        let answer = |part, json: &str, nanos| days::PartAnswer {
+            part,
+            text: json.to_string(),
+            json: json.to_string(),
+            time: Duration::from_nanos(nanos),
+        };
+        let solved = days::Solved {
+            parse_time: Duration::from_nanos(1500),
+            answers: vec![
+                answer(1, "952408144115", 20),
+                answer(2, "18446744073709551615", 300),
+            ],
+        };
+        assert_eq!(
+            render_json(18, &solved),
+            "{\"day\":18,\"part1\":952408144115,\"part2\":18446744073709551615,\"timings_ns\":{\"parse\":1500,\"part1\":20,\"part2\":300}}"
+        );
+    }
+}
@@ -0,0 +1,501 @@
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+pub mod math;
+
+/**
+ * A day's puzzle. The input is parsed once into a typed value which both
+ * parts then solve, so runners and benchmarks can treat every day the same.
+ */
+pub trait Solution {
+    const DAY: u32;
+
+    type Input<'a>;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part1(input: &Self::Input<'_>) -> Self::Part1;
+    fn part2(input: &Self::Input<'_>) -> Self::Part2;
+}
+
+/**
+ * A part's answer: displayed for people and rendered as a JSON value for
+ * tools. Integers are written out in full so no precision is lost.
+ */
+pub trait Answer: fmt::Display {
+    fn to_json(&self) -> String;
+}
+
+macro_rules! integer_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn to_json(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+integer_answer!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+impl Answer for String {
+    fn to_json(&self) -> String {
+        json_string(self)
+    }
+}
+
+/**
+ * Quote and escape `s` as a JSON string.
+ */
+pub fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/**
+ * Answer for a part that has no puzzle, such as part 2 on the last day.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoAnswer;
+
+impl fmt::Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "-")
+    }
+}
+
+impl Answer for NoAnswer {
+    fn to_json(&self) -> String {
+        "null".to_string()
+    }
+}
+
+/**
+ * Answer for a part that a well-formed input may still have no solution to,
+ * shown like [`NoAnswer`] when it has none.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaybeAnswer<T>(pub Option<T>);
+
+impl<T: Answer> fmt::Display for MaybeAnswer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Some(answer) => answer.fmt(f),
+            None => NoAnswer.fmt(f),
+        }
+    }
+}
+
+impl<T: Answer> Answer for MaybeAnswer<T> {
+    fn to_json(&self) -> String {
+        match &self.0 {
+            Some(answer) => answer.to_json(),
+            None => NoAnswer.to_json(),
+        }
+    }
+}
+
+/**
+ * Malformed puzzle input. Line and column start at 1 and point at the
+ * offending text.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+/**
+ * Line and column of `text` within `source`. `text` is expected to be a slice
+ * of `source`; otherwise its first occurrence is used, or the start of
+ * `source` if there is none.
+ */
+fn position(source: &str, text: &str) -> (usize, usize) {
+    let start = source.as_ptr() as usize;
+    let offset = match (text.as_ptr() as usize).checked_sub(start) {
+        Some(offset) if offset + text.len() <= source.len() => offset,
+        _ => source.find(text).unwrap_or(0),
+    };
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+impl ParseError {
+    /**
+     * Error at `text`, positioned relative to the start of `source`.
+     */
+    pub fn new(source: &str, text: &str, message: impl Into<String>) -> Self {
+        let (line, column) = position(source, text);
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /**
+     * Reposition an error raised while parsing `part` so that it is relative
+     * to `source`, which contains it.
+     */
+    pub fn within(mut self, source: &str, part: &str) -> Self {
+        let (line, column) = position(source, part);
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " at {:?}", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for ParseError {}
+
+/**
+ * Parse every line of `input`, reporting errors relative to the whole input.
+ */
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parse_line: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse_line(line).map_err(|err| err.within(input, line)))
+        .collect()
+}
+
+/**
+ * Parse `text`, a slice of `source`, as a number.
+ */
+pub fn parse_number<T: FromStr>(source: &str, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(source, text, "invalid number"))
+}
+
+/**
+ * Parse every character of `line`, rejecting with `message` the first one for
+ * which `parse_char` returns None.
+ */
+pub fn parse_chars<T>(
+    line: &str,
+    mut parse_char: impl FnMut(char) -> Option<T>,
+    message: &str,
+) -> Result<Vec<T>, ParseError> {
+    line.char_indices()
+        .map(|(i, c)| {
+            parse_char(c).ok_or_else(|| ParseError::new(line, &line[i..i + c.len_utf8()], message))
+        })
+        .collect()
+}
+
+/**
+ * Split `text`, a slice of `source`, around the first `delimiter`.
+ */
+pub fn split_once<'a>(
+    source: &str,
+    text: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(source, text, format!("expected {delimiter:?}")))
+}
+
+/**
+ * Where to read a day's puzzle input from.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /** A single file, used whatever the day. */
+    File(PathBuf),
+    /** Standard input. */
+    Stdin,
+    /** `<dir>/dayNN.txt`, so several inputs can live side by side. */
+    Directory(PathBuf),
+    /** `dayNN/input.txt` in the workspace. */
+    DayDirectory,
+}
+
+/**
+ * Failure to read a day's puzzle input.
+ */
+#[derive(Debug)]
+pub struct InputError {
+    pub name: String,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.error.kind() {
+            io::ErrorKind::NotFound => write!(f, "input file {} not found", self.name),
+            _ => write!(f, "failed to read {}: {}", self.name, self.error),
+        }
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl InputSource {
+    /**
+     * The source selected by a `--input <file|->` or `--input-dir <dir>`
+     * command line option, or None for any other option.
+     */
+    pub fn from_option(option: &str, value: &str) -> Option<InputSource> {
+        match (option, value) {
+            ("--input", "-") => Some(InputSource::Stdin),
+            ("--input", path) => Some(InputSource::File(path.into())),
+            ("--input-dir", dir) => Some(InputSource::Directory(dir.into())),
+            _ => None,
+        }
+    }
+
+    /**
+     * Whether the source can only be used for a single day.
+     */
+    pub fn is_single(&self) -> bool {
+        matches!(self, InputSource::File(_) | InputSource::Stdin)
+    }
+
+    /**
+     * Name of the input for `day`, as shown in error messages.
+     */
+    pub fn name(&self, day: u32) -> String {
+        match self {
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+            InputSource::Directory(dir) => {
+                dir.join(format!("day{day:02}.txt")).display().to_string()
+            }
+            InputSource::DayDirectory => format!("day{day:02}/input.txt"),
+        }
+    }
+
+    pub fn read(&self, day: u32) -> Result<String, InputError> {
+        let result = match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map(|_| input)
+            }
+            _ => fs::read_to_string(self.name(day)),
+        };
+        result.map_err(|error| InputError {
+            name: self.name(day),
+            error,
+        })
+    }
+
+    /**
+     * Open the input for `day` to be read incrementally instead of all at
+     * once.
+     */
+    pub fn open(&self, day: u32) -> Result<Box<dyn BufRead>, InputError> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            _ => match fs::File::open(self.name(day)) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(error) => Err(InputError {
+                    name: self.name(day),
+                    error,
+                }),
+            },
+        }
+    }
+}
+
+/**
+ * The input selected by a day binary's arguments: input.txt in the working
+ * directory unless `--input <file|->` or `--input-dir <dir>` is given.
+ */
+pub fn input_from_args(args: &[String]) -> Result<InputSource, String> {
+    match args {
+        [] => Ok(InputSource::File("input.txt".into())),
+        [option, value] => InputSource::from_option(option, value)
+            .ok_or_else(|| format!("unexpected argument '{option}'")),
+        _ => Err("Usage: [--input <file|->] [--input-dir <dir>]".to_string()),
+    }
+}
+
+/**
+ * Entry point for a day's binary, solving the input chosen by
+ * [`input_from_args`].
+ */
+pub fn main<S: Solution>() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let source = match input_from_args(&args) {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let file_contents = match source.read(S::DAY) {
+        Ok(file_contents) => file_contents,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match S::parse(&file_contents) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}: {err}", source.name(S::DAY));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("Part 1: {}", S::part1(&input));
+    println!("Part 2: {}", S::part2(&input));
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "a: 1
+b: 2
+c: x3";
+
+    fn parse_line(line: &str) -> Result<(&str, u32), ParseError> {
+        let (name, value) = split_once(line, line, ": ")?;
+        Ok((name, parse_number(line, value)?))
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(
+            parse_lines("a: 1\nb: 2", parse_line),
+            Ok(vec![("a", 1), ("b", 2)])
+        );
+        assert_eq!(
+            parse_lines(SAMPLE, parse_line),
+            Err(ParseError {
+                line: 3,
+                column: 4,
+                text: "x3".to_string(),
+                message: "invalid number".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_chars() {
+        let digit = |c: char| c.to_digit(10);
+        assert_eq!(
+            parse_chars("123", digit, "invalid digit"),
+            Ok(vec![1, 2, 3])
+        );
+        let err = parse_lines("12\n3é4", |line| parse_chars(line, digit, "invalid digit"));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "line 2, column 2: invalid digit at \"é\""
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let err = parse_lines(SAMPLE, parse_line).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 4: invalid number at \"x3\""
+        );
+        let err = parse_lines("a 1", parse_line).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected \": \" at \"a 1\""
+        );
+    }
+
+    #[test]
+    fn test_input_source() {
+        let dir = InputSource::from_option("--input-dir", "inputs").unwrap();
+        assert_eq!(
+            dir.name(7),
+            format!("inputs{}day07.txt", std::path::MAIN_SEPARATOR)
+        );
+        assert!(!dir.is_single());
+        assert_eq!(
+            InputSource::from_option("--input", "-"),
+            Some(InputSource::Stdin)
+        );
+        assert_eq!(InputSource::from_option("--part", "1"), None);
+        assert_eq!(InputSource::DayDirectory.name(7), "day07/input.txt");
+        let err = InputSource::File("missing.txt".into()).read(7).unwrap_err();
+        assert_eq!(err.to_string(), "input file missing.txt not found");
+        let err = InputSource::File("missing.txt".into())
+            .open(7)
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "input file missing.txt not found");
+        let args = |args: &[&str]| -> Vec<String> { args.iter().map(|a| a.to_string()).collect() };
+        assert_eq!(
+            input_from_args(&args(&[])),
+            Ok(InputSource::File("input.txt".into()))
+        );
+        assert_eq!(
+            input_from_args(&args(&["--input", "-"])),
+            Ok(InputSource::Stdin)
+        );
+        assert!(input_from_args(&args(&["--part", "1"])).is_err());
+        assert!(input_from_args(&args(&["--input"])).is_err());
+    }
+
+    #[test]
+    fn test_answer_json() {
+        assert_eq!(u64::MAX.to_json(), "18446744073709551615");
+        assert_eq!((-42i64).to_json(), "-42");
+        assert_eq!(NoAnswer.to_json(), "null");
+        assert_eq!(MaybeAnswer(Some(7u32)).to_json(), "7");
+        assert_eq!(MaybeAnswer::<u32>(None).to_json(), "null");
+        assert_eq!(MaybeAnswer::<u32>(None).to_string(), "-");
+        assert_eq!(
+            "say \"hi\"\n".to_string().to_json(),
+            "\"say \\\"hi\\\"\\n\""
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/**
+ * Primitive integer types the number-theory helpers are generic over.
+ */
+pub trait Integer:
+    Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn abs(self) -> Self;
+}
+
+macro_rules! integer {
+    (signed: $($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+        })*
+    };
+    (unsigned: $($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn abs(self) -> Self {
+                self
+            }
+        })*
+    };
+}
+
+integer!(signed: i32, i64, i128, isize);
+integer!(unsigned: u32, u64, u128, usize);
+
+/**
+ * Greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+ */
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/**
+ * Least common multiple, or None if it overflows. It is 0 if either number
+ * is.
+ */
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a.abs() / gcd(a, b)).checked_mul(b.abs())
+}
+
+/**
+ * Least common multiple. Panics on overflow.
+ */
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+/**
+ * Least common multiple of all of `numbers`, or None if it overflows.
+ */
+pub fn checked_lcm_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    let mut numbers = numbers.into_iter();
+    let first = numbers.next()?;
+    numbers.try_fold(first, checked_lcm)
+}
+
+/**
+ * Least common multiple of all of `numbers`. Panics on overflow or if there
+ * are none.
+ */
+pub fn lcm_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> T {
+    checked_lcm_all(numbers).expect("lcm of no numbers or overflowed")
+}
+
+/**
+ * Extended Euclid: `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+ */
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/**
+ * Chinese Remainder Theorem for moduli that need not be coprime. Solves
+ * `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair, returning
+ * `(x, m)` with `0 <= x < m` where `m` is the lcm of the moduli. Returns None
+ * if the congruences conflict, a modulus is not positive, or `m` overflows.
+ */
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    let mut solution = (0, 1);
+    for (residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+        let (a1, m1) = solution;
+        let a2 = residue.rem_euclid(modulus);
+        let (g, p, _) = extended_gcd(m1, modulus);
+        if (a2 - a1) % g != 0 {
+            return None;
+        }
+        let m = (m1 / g).checked_mul(modulus)?;
+        // x = a1 + k * m1 where k * m1 ≡ a2 - a1 (mod modulus).
+        let k = ((a2 - a1) / g).checked_mul(p)?.rem_euclid(modulus / g);
+        solution = ((a1 + k.checked_mul(m1)?).rem_euclid(m), m);
+    }
+    Some(solution)
+}
+
+/**
+ * Smallest value not less than `min` that is congruent to `residue` modulo
+ * `modulus`.
+ */
+pub fn next_congruent(residue: i128, modulus: i128, min: i128) -> i128 {
+    min + (residue - min).rem_euclid(modulus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0usize, 0), 0);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm_all([2usize, 3, 4, 5]), 60);
+        assert_eq!(checked_lcm(u32::MAX, u32::MAX - 1), None);
+        assert_eq!(checked_lcm_all(Vec::<u32>::new()), None);
+        assert_eq!(checked_lcm_all([0u32, 5]), Some(0));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        let (g, x, y) = extended_gcd(-7, 3);
+        assert_eq!(g, 1);
+        assert_eq!(-7 * x + 3 * y, 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Moduli sharing a factor.
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([(0, 0)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(next_congruent(0, 6, 3), 6);
+        assert_eq!(next_congruent(4, 6, 4), 4);
+        assert_eq!(next_congruent(23, 105, 100), 128);
+    }
+}
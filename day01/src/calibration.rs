@@ -0,0 +1,498 @@
+use common::parse_lines;
+use common::parse_number;
+use common::split_once;
+use common::ParseError;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+
+/**
+ * The tokens that count as digits on a calibration line, each with its value.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    tokens: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /**
+     * The digits 0 to 9.
+     */
+    pub fn digits() -> Self {
+        Vocabulary {
+            tokens: (0..10).map(|d| (d.to_string(), d)).collect(),
+        }
+    }
+
+    /**
+     * The digits plus the English words "one" to "nine".
+     */
+    pub fn english() -> Self {
+        let words = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        (1..)
+            .zip(words)
+            .fold(Self::digits(), |vocabulary, (value, word)| {
+                vocabulary.with(word, value)
+            })
+    }
+
+    /**
+     * Add a token. Panics if it is empty.
+     */
+    pub fn with(mut self, token: impl Into<String>, value: u32) -> Self {
+        let token = token.into();
+        assert!(!token.is_empty(), "vocabulary tokens must not be empty");
+        self.tokens.push((token, value));
+        self
+    }
+
+    /**
+     * Parse one `<token> = <value>` entry per line, such as `uno = 1`.
+     */
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let tokens = parse_lines(text, |line| {
+            let (token, value) = split_once(line, line, "=")?;
+            let (token, value) = (token.trim(), value.trim());
+            if token.is_empty() {
+                return Err(ParseError::new(line, line, "empty token"));
+            }
+            Ok((token.to_string(), parse_number(line, value)?))
+        })?;
+        Ok(Vocabulary { tokens })
+    }
+}
+
+/**
+ * A vocabulary compiled into an Aho-Corasick automaton, so every token ending
+ * at a byte is found by a single transition.
+ */
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    /** Complete transition table: failure links are already followed. */
+    next: Vec<[usize; 256]>,
+    /** (length, value) of every token ending at each state, longest first. */
+    outputs: Vec<Vec<(usize, u32)>>,
+}
+
+impl Matcher {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        // Build the trie, with 0 meaning "no edge" until the links are filled.
+        let mut next = vec![[0; 256]];
+        let mut outputs: Vec<Vec<(usize, u32)>> = vec![Vec::new()];
+        for (token, value) in &vocabulary.tokens {
+            let mut state = 0;
+            for &byte in token.as_bytes() {
+                if next[state][byte as usize] == 0 {
+                    next.push([0; 256]);
+                    outputs.push(Vec::new());
+                    next[state][byte as usize] = next.len() - 1;
+                }
+                state = next[state][byte as usize];
+            }
+            outputs[state].push((token.len(), *value));
+        }
+        // Breadth-first, point missing edges at the failure state's edges and
+        // inherit the outputs of the failure state.
+        let mut fail = vec![0; next.len()];
+        let mut queue: VecDeque<usize> = next[0].iter().copied().filter(|&s| s != 0).collect();
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+            outputs[state].sort_by_key(|&(len, _)| Reverse(len));
+            let edges = next[state];
+            for (byte, child) in edges.into_iter().enumerate() {
+                if child == 0 {
+                    next[state][byte] = next[fail[state]][byte];
+                } else {
+                    fail[child] = next[fail[state]][byte];
+                    queue.push_back(child);
+                }
+            }
+        }
+        Matcher { next, outputs }
+    }
+
+    /**
+     * Calibration value of a single line, or 0 if it has no tokens.
+     */
+    pub fn line_value(&self, line: &str) -> u32 {
+        self.scan_line(line.as_bytes()).value().unwrap_or(0)
+    }
+
+    fn scan_line(&self, line: &[u8]) -> Calibration {
+        let mut scan = LineScan::default();
+        for &byte in line {
+            scan.push(self, byte);
+        }
+        scan.finish()
+    }
+}
+
+/**
+ * A token found on a line, by byte offset.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub len: usize,
+    pub value: u32,
+}
+
+impl Match {
+    fn end(&self) -> usize {
+        self.start + self.len
+    }
+}
+
+/**
+ * The first and last tokens of a line. Tokens are ordered by where they start;
+ * of two starting at the same place the longer one counts.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Calibration {
+    pub first: Option<Match>,
+    pub last: Option<Match>,
+    /** The first two tokens that share bytes without one containing the
+     * other, like "two" and "one" in "twone". */
+    pub overlap: Option<(Match, Match)>,
+}
+
+impl Calibration {
+    /**
+     * The first and last values combined into a two-digit number, or None if
+     * the line has no tokens.
+     */
+    pub fn value(&self) -> Option<u32> {
+        Some(self.first?.value * 10 + self.last?.value)
+    }
+}
+
+/**
+ * State of the automaton part way through a line.
+ */
+#[derive(Debug, Default)]
+struct LineScan {
+    state: usize,
+    len: usize,
+    /** The token reaching furthest, among those ending before this byte. */
+    furthest: Option<Match>,
+    calibration: Calibration,
+}
+
+impl LineScan {
+    fn push(&mut self, matcher: &Matcher, byte: u8) {
+        self.state = matcher.next[self.state][byte as usize];
+        self.len += 1;
+        for &(len, value) in &matcher.outputs[self.state] {
+            let found = Match {
+                start: self.len - len,
+                len,
+                value,
+            };
+            let Calibration {
+                first,
+                last,
+                overlap,
+            } = &mut self.calibration;
+            // Tokens without partial overlaps nest, so if any earlier token
+            // straddles this one's start, the furthest reaching one does.
+            if let Some(furthest) = self.furthest {
+                if overlap.is_none() && furthest.start < found.start && found.start < furthest.end()
+                {
+                    *overlap = Some((furthest, found));
+                }
+            }
+            // A longer token starting at the same place ends later, so it
+            // replaces the shorter one.
+            let longer = |other: Match| found.start == other.start && found.len > other.len;
+            if first.is_none_or(|first| found.start < first.start || longer(first)) {
+                *first = Some(found);
+            }
+            if last.is_none_or(|last| found.start > last.start || longer(last)) {
+                *last = Some(found);
+            }
+        }
+        // The longest token ending here reaches as far as any so far and
+        // starts earliest.
+        if let Some(&(len, value)) = matcher.outputs[self.state].first() {
+            self.furthest = Some(Match {
+                start: self.len - len,
+                len,
+                value,
+            });
+        }
+    }
+
+    fn finish(self) -> Calibration {
+        self.calibration
+    }
+}
+
+/**
+ * Calibrations of every line read from `reader`, in constant memory however
+ * long the lines or the input are.
+ */
+pub struct Calibrations<'m, R> {
+    reader: R,
+    matcher: &'m Matcher,
+}
+
+impl<'m, R: BufRead> Calibrations<'m, R> {
+    pub fn new(reader: R, matcher: &'m Matcher) -> Self {
+        Calibrations { reader, matcher }
+    }
+}
+
+impl<R: BufRead> Iterator for Calibrations<'_, R> {
+    type Item = io::Result<Calibration>;
+
+    fn next(&mut self) -> Option<io::Result<Calibration>> {
+        let mut scan = LineScan::default();
+        loop {
+            let buffer = match self.reader.fill_buf() {
+                Ok(buffer) => buffer,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Some(Err(err)),
+            };
+            if buffer.is_empty() {
+                // A final line without a newline still counts.
+                return (scan.len > 0).then(|| Ok(scan.finish()));
+            }
+            let end = buffer.iter().position(|&byte| byte == b'\n');
+            for &byte in &buffer[..end.unwrap_or(buffer.len())] {
+                scan.push(self.matcher, byte);
+            }
+            let consumed = end.map_or(buffer.len(), |end| end + 1);
+            self.reader.consume(consumed);
+            if end.is_some() {
+                return Some(Ok(scan.finish()));
+            }
+        }
+    }
+}
+
+/**
+ * Sum of the calibration values of every line read from `reader`. Lines
+ * without tokens contribute nothing.
+ */
+pub fn calibrate(reader: impl BufRead, matcher: &Matcher) -> io::Result<u64> {
+    let mut total = 0;
+    for calibration in Calibrations::new(reader, matcher) {
+        total += u64::from(calibration?.value().unwrap_or(0));
+    }
+    Ok(total)
+}
+
+/**
+ * Why a line was flagged by [`audit`].
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /** No tokens, so the line adds nothing to the total. */
+    NoValue,
+    /** Two tokens sharing letters, which may have been meant as one. */
+    Overlap(String, String),
+}
+
+/**
+ * A flagged line, numbered from 1.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub text: String,
+    pub issue: Issue,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.issue {
+            Issue::NoValue => write!(f, "line {}: no calibration value", self.line)?,
+            Issue::Overlap(first, second) => {
+                write!(f, "line {}: {first:?} overlaps {second:?}", self.line)?
+            }
+        }
+        write!(f, " in {:?}", self.text)
+    }
+}
+
+/**
+ * Result of auditing a calibration document.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Audit {
+    pub lines: usize,
+    pub total: u64,
+    /** Lines whose value really is 0, as opposed to having none. */
+    pub zero_values: usize,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Audit {
+    fn count(&self, no_value: bool) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| (diagnostic.issue == Issue::NoValue) == no_value)
+            .count()
+    }
+}
+
+impl fmt::Display for Audit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        writeln!(
+            f,
+            "{} lines, total {}: {} without a calibration value, {} with value 0, {} with overlapping matches",
+            self.lines,
+            self.total,
+            self.count(true),
+            self.zero_values,
+            self.count(false)
+        )
+    }
+}
+
+/**
+ * Calibrate every line read from `reader`, flagging lines without a value and
+ * lines with overlapping tokens. Only one line is held in memory at a time,
+ * plus the text of the flagged ones.
+ */
+pub fn audit(mut reader: impl BufRead, matcher: &Matcher) -> io::Result<Audit> {
+    let mut audit = Audit::default();
+    let mut line = Vec::new();
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        let text = line.strip_suffix(b"\n").unwrap_or(&line);
+        let text = text.strip_suffix(b"\r").unwrap_or(text);
+        audit.lines += 1;
+        let calibration = matcher.scan_line(text);
+        let token = |m: Match| String::from_utf8_lossy(&text[m.start..m.end()]).into_owned();
+        let issue = match (calibration.value(), calibration.overlap) {
+            (None, _) => Some(Issue::NoValue),
+            (Some(value), overlap) => {
+                audit.total += u64::from(value);
+                audit.zero_values += usize::from(value == 0);
+                overlap.map(|(first, second)| Issue::Overlap(token(first), token(second)))
+            }
+        };
+        if let Some(issue) = issue {
+            audit.diagnostics.push(Diagnostic {
+                line: audit.lines,
+                text: String::from_utf8_lossy(text).into_owned(),
+                issue,
+            });
+        }
+        line.clear();
+    }
+    Ok(audit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    static SAMPLE: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    #[test]
+    fn test_matcher() {
+        let matcher = Matcher::new(&Vocabulary::english());
+        let calibration = Calibrations::new("xtwone3four".as_bytes(), &matcher)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            calibration.first,
+            Some(Match {
+                start: 1,
+                len: 3,
+                value: 2
+            })
+        );
+        assert_eq!(calibration.last.map(|last| last.value), Some(4));
+        assert_eq!(matcher.line_value("twone"), 21);
+        assert_eq!(matcher.line_value("no digits"), 0);
+        // Overlapping tokens of different lengths starting together.
+        let roman = Matcher::new(
+            &Vocabulary::digits()
+                .with("i", 1)
+                .with("ii", 2)
+                .with("iv", 4)
+                .with("v", 5),
+        );
+        assert_eq!(roman.line_value("xivx"), 45);
+        assert_eq!(roman.line_value("xvi"), 51);
+        assert_eq!(roman.line_value("ii"), 21);
+    }
+
+    #[test]
+    fn test_vocabulary_parse() {
+        let vocabulary = Vocabulary::parse("uno = 1\ndos=2\ncero = 0").unwrap();
+        let matcher = Matcher::new(&vocabulary);
+        assert_eq!(matcher.line_value("ceroxunodos"), 2);
+        assert_eq!(
+            Vocabulary::parse("uno = 1\ndos 2").unwrap_err().to_string(),
+            "line 2, column 1: expected \"=\" at \"dos 2\""
+        );
+        assert!(Vocabulary::parse(" = 3").is_err());
+    }
+
+    #[test]
+    fn test_calibrate() {
+        let matcher = Matcher::new(&Vocabulary::english());
+        assert_eq!(calibrate(SAMPLE.as_bytes(), &matcher).unwrap(), 281);
+        // Tiny buffers split lines and tokens across reads.
+        let reader = BufReader::with_capacity(3, SAMPLE.as_bytes());
+        assert_eq!(calibrate(reader, &matcher).unwrap(), 281);
+        let lines = Calibrations::new("1\n\nabc\n2".as_bytes(), &matcher)
+            .map(|calibration| calibration.unwrap().value())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![Some(11), None, None, Some(22)]);
+    }
+
+    #[test]
+    fn test_audit() {
+        let matcher = Matcher::new(&Vocabulary::english());
+        let audit = audit(SAMPLE.as_bytes(), &matcher).unwrap();
+        assert_eq!(audit.total, 281);
+        assert_eq!(
+            audit.diagnostics,
+            vec![
+                Diagnostic {
+                    line: 2,
+                    text: "eightwothree".to_string(),
+                    issue: Issue::Overlap("eight".to_string(), "two".to_string()),
+                },
+                Diagnostic {
+                    line: 4,
+                    text: "xtwone3four".to_string(),
+                    issue: Issue::Overlap("two".to_string(), "one".to_string()),
+                },
+                Diagnostic {
+                    line: 6,
+                    text: "zoneight234".to_string(),
+                    issue: Issue::Overlap("one".to_string(), "eight".to_string()),
+                },
+            ]
+        );
+        let matcher = Matcher::new(&Vocabulary::digits());
+        let audit = super::audit("a1b\r\nnothing\n00\n".as_bytes(), &matcher).unwrap();
+        assert_eq!(
+            audit.to_string(),
+            "line 2: no calibration value in \"nothing\"
+3 lines, total 11: 1 without a calibration value, 1 with value 0, 0 with overlapping matches
+"
+        );
+    }
+}
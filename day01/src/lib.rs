@@ -0,0 +1,73 @@
+pub mod calibration;
+
+use calibration::calibrate;
+use calibration::Matcher;
+use calibration::Vocabulary;
+use common::ParseError;
+use common::Solution;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    /** Matches the digits 0-9, as part 1 reads lines. */
+    pub static ref DIGIT_MATCHER: Matcher = Matcher::new(&Vocabulary::digits());
+    /** Matches digits and spelled-out numbers, as part 2 reads lines. */
+    pub static ref ENGLISH_MATCHER: Matcher = Matcher::new(&Vocabulary::english());
+}
+
+pub fn parse_line_p1(line: &str) -> u32 {
+    DIGIT_MATCHER.line_value(line)
+}
+
+pub fn parse_line_p2(line: &str) -> u32 {
+    ENGLISH_MATCHER.line_value(line)
+}
+
+fn sum_calibrations(input: &str, matcher: &Matcher) -> u64 {
+    calibrate(input.as_bytes(), matcher).expect("reading from memory")
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+
+    type Input<'a> = &'a str;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> u64 {
+        sum_calibrations(input, &DIGIT_MATCHER)
+    }
+
+    fn part2(input: &&str) -> u64 {
+        sum_calibrations(input, &ENGLISH_MATCHER)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(parse_line_p1("1abc2"), 12);
+        assert_eq!(parse_line_p1("pqr3stu8vwx"), 38);
+        assert_eq!(parse_line_p1("a1b2c3d4e5f"), 15);
+        assert_eq!(parse_line_p1("treb7uchet"), 77);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(parse_line_p2("two1nine"), 29);
+        assert_eq!(parse_line_p2("eightwothree"), 83);
+        assert_eq!(parse_line_p2("abcone2threexyz"), 13);
+        assert_eq!(parse_line_p2("xtwone3four"), 24);
+        assert_eq!(parse_line_p2("4nineeightseven2"), 42);
+        assert_eq!(parse_line_p2("zoneight234"), 14);
+        assert_eq!(parse_line_p2("7pqrstsixteen"), 76);
+    }
+}
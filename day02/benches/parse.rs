@@ -0,0 +1,110 @@
+//! Compares the hand-written game parser with the regex parser it replaced,
+//! on generated inputs. Run with `cargo bench -p day02`.
+
+use day02::parse_games;
+use day02::Cubes;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::hint::black_box;
+use std::time::Duration;
+use std::time::Instant;
+
+lazy_static! {
+    static ref GAME_REGEX: Regex =
+        Regex::new(r"Game (\d+): (.*)").expect("Failed to parse game regex");
+    static ref SAMPLE_REGEX: Regex =
+        Regex::new(r"(\d+) (blue|green|red)").expect("Failed to parse sample regex");
+}
+
+/**
+ * The id and samples of one game, as the regex parser returned them.
+ */
+fn parse_game_regex(line: &str) -> Option<(u32, Vec<Cubes<'_>>)> {
+    let captures = GAME_REGEX.captures(line)?;
+    let id = captures[1].parse().ok()?;
+    let samples = captures
+        .get(2)?
+        .as_str()
+        .split(';')
+        .map(|sample| {
+            let mut cubes = Cubes::default();
+            for captures in SAMPLE_REGEX.captures_iter(sample) {
+                let count: u8 = captures[1].parse().ok()?;
+                cubes.add(captures.get(2)?.as_str(), u32::from(count));
+            }
+            Some(cubes)
+        })
+        .collect::<Option<_>>()?;
+    Some((id, samples))
+}
+
+/**
+ * `games` lines of puzzle-like input from a fixed pseudo-random sequence.
+ */
+fn generate(games: usize) -> String {
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut random = |n: u64| {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) % n
+    };
+    let mut input = String::new();
+    for id in 1..=games {
+        let mut samples = Vec::new();
+        for _ in 0..1 + random(6) {
+            let mut entries = Vec::new();
+            for colour in ["red", "green", "blue"] {
+                if random(3) > 0 {
+                    entries.push(format!("{} {colour}", 1 + random(20)));
+                }
+            }
+            if entries.is_empty() {
+                entries.push("1 red".to_string());
+            }
+            samples.push(entries.join(", "));
+        }
+        input += &format!("Game {id}: {}\n", samples.join("; "));
+    }
+    input
+}
+
+/**
+ * Fastest of `runs` timings of `f`.
+ */
+fn fastest<T>(runs: usize, mut f: impl FnMut() -> T) -> Duration {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .expect("at least one run")
+}
+
+fn main() {
+    for games in [1_000, 100_000, 1_000_000] {
+        let input = generate(games);
+        let parsed = parse_games(&input).expect("generated input is valid");
+        let hand: Vec<(u32, Vec<Cubes>)> = parsed
+            .iter()
+            .map(|game| (game.id, game.samples().map(Cubes::from).collect()))
+            .collect();
+        let regex: Vec<(u32, Vec<Cubes>)> = input
+            .lines()
+            .map(|line| parse_game_regex(line).expect("generated input is valid"))
+            .collect();
+        assert_eq!(hand, regex);
+        let runs = if games < 1_000_000 { 10 } else { 3 };
+        let hand = fastest(runs, || parse_games(&input));
+        let regex = fastest(runs, || {
+            input.lines().map(parse_game_regex).collect::<Vec<_>>()
+        });
+        println!(
+            "{games:>9} games, {:>6.1} MB: hand-written {hand:>10.3?}, regex {regex:>10.3?} ({:.1}x)",
+            input.len() as f64 / 1e6,
+            regex.as_secs_f64() / hand.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,501 @@
+use common::parse_lines;
+use common::parse_number;
+use common::split_once;
+use common::ParseError;
+use common::Solution;
+use std::fmt;
+use std::num::IntErrorKind;
+use std::num::ParseIntError;
+use std::ops::Range;
+
+/**
+ * A number of cubes of each colour, in the order the colours were first seen.
+ * Colours that are not listed count as 0.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cubes<'a> {
+    counts: Vec<(&'a str, u32)>,
+}
+
+impl<'a> Cubes<'a> {
+    /**
+     * Parse a list of counts such as `12 red, 13 green, 14 blue`.
+     */
+    pub fn parse(text: &'a str) -> Result<Self, ParseError> {
+        let mut cubes = Cubes::default();
+        for entry in text.split(',') {
+            let (colour, count) = parse_entry(text, entry)?;
+            cubes.add(colour, count);
+        }
+        Ok(cubes)
+    }
+
+    /**
+     * Add `count` cubes of `colour`.
+     */
+    pub fn with(mut self, colour: &'a str, count: u32) -> Self {
+        self.add(colour, count);
+        self
+    }
+
+    pub fn add(&mut self, colour: &'a str, count: u32) {
+        match self.counts.iter_mut().find(|(c, _)| *c == colour) {
+            Some((_, total)) => *total += count,
+            None => self.counts.push((colour, count)),
+        }
+    }
+
+    pub fn get(&self, colour: &str) -> u32 {
+        self.iter()
+            .find(|&(c, _)| c == colour)
+            .map_or(0, |(_, count)| count)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, u32)> + '_ {
+        self.counts.iter().copied()
+    }
+
+    /**
+     * Whether these cubes include at least as many of every colour as
+     * `other` lists, such as the counts of other cubes or of a sample.
+     */
+    pub fn contains<'b>(&self, other: impl IntoIterator<Item = (&'b str, u32)>) -> bool {
+        other
+            .into_iter()
+            .all(|(colour, count)| count <= self.get(colour))
+    }
+
+    /**
+     * Raise each count to at least the one `other` lists.
+     */
+    pub fn cover(&mut self, other: impl IntoIterator<Item = (&'a str, u32)>) {
+        for (colour, count) in other {
+            match self.counts.iter_mut().find(|(c, _)| *c == colour) {
+                Some((_, total)) => *total = (*total).max(count),
+                None => self.counts.push((colour, count)),
+            }
+        }
+    }
+
+    /**
+     * Product of the counts of `colours`.
+     */
+    pub fn power(&self, colours: &[&str]) -> u64 {
+        colours
+            .iter()
+            .map(|colour| u64::from(self.get(colour)))
+            .product()
+    }
+}
+
+impl fmt::Display for Cubes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (colour, count)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{count} {colour}")?;
+        }
+        Ok(())
+    }
+}
+
+/**
+ * The cubes shown in one sample. Each colour appears once, with the counts
+ * of any repeats added up.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample<'a> {
+    entries: &'a [(&'a str, u32)],
+}
+
+impl<'a> Sample<'a> {
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, u32)> + 'a {
+        self.entries.iter().copied()
+    }
+
+    pub fn get(&self, colour: &str) -> u32 {
+        self.iter()
+            .find(|&(c, _)| c == colour)
+            .map_or(0, |(_, count)| count)
+    }
+}
+
+impl<'a> From<Sample<'a>> for Cubes<'a> {
+    fn from(sample: Sample<'a>) -> Self {
+        Cubes {
+            counts: sample.entries.to_vec(),
+        }
+    }
+}
+
+/**
+ * One game of a set of `Games`: its id and the samples shown.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Game<'a> {
+    pub id: u32,
+    /** The entries of every sample, one sample after another. */
+    entries: &'a [(&'a str, u32)],
+    /** Where each sample ends in `entries`. */
+    sample_ends: &'a [usize],
+}
+
+impl<'a> Game<'a> {
+    pub fn samples(&self) -> impl Iterator<Item = Sample<'a>> + 'a {
+        let entries = self.entries;
+        let mut start = 0;
+        self.sample_ends.iter().map(move |&end| {
+            let sample = Sample {
+                entries: &entries[start..end],
+            };
+            start = end;
+            sample
+        })
+    }
+
+    /**
+     * Whether every sample could have been drawn from `bag`.
+     */
+    pub fn is_possible(&self, bag: &Cubes) -> bool {
+        self.samples().all(|sample| bag.contains(sample.iter()))
+    }
+
+    /**
+     * The smallest bag every sample could have been drawn from.
+     */
+    pub fn minimal_bag(&self) -> Cubes<'a> {
+        let mut bag = Cubes::default();
+        for sample in self.samples() {
+            bag.cover(sample.iter());
+        }
+        bag
+    }
+}
+
+/**
+ * Where one game's entries and sample ends are stored in `Games`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GameRecord {
+    id: u32,
+    entries: Range<usize>,
+    samples: Range<usize>,
+}
+
+/**
+ * Every game of an input. The checked entries of all the samples are kept
+ * in one list, with games and samples as ranges of it, so parsing does not
+ * allocate per game or per sample.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Games<'a> {
+    /** `(colour, count)` of every sample of every game, in input order. */
+    entries: Vec<(&'a str, u32)>,
+    /** Where each sample ends within its game's entries. */
+    sample_ends: Vec<usize>,
+    games: Vec<GameRecord>,
+}
+
+impl<'a> Games<'a> {
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Game<'_>> {
+        self.games.iter().map(|game| Game {
+            id: game.id,
+            entries: &self.entries[game.entries.clone()],
+            sample_ends: &self.sample_ends[game.samples.clone()],
+        })
+    }
+
+    /**
+     * Parse one game onto the end of the list.
+     */
+    fn parse_game(&mut self, line: &'a str) -> Result<(), ParseError> {
+        let rest = line
+            .strip_prefix("Game ")
+            .ok_or_else(|| ParseError::new(line, line, "expected \"Game <id>: <samples>\""))?;
+        let (id, samples) = split_once(line, rest, ": ")?;
+        let id: u32 = parse_number(line, id)?;
+        let (first_entry, first_sample) = (self.entries.len(), self.sample_ends.len());
+        for sample in samples.split(';') {
+            let start = self.entries.len();
+            for entry in sample.split(',') {
+                let (colour, count) = parse_entry(line, entry)?;
+                match self.entries[start..].iter_mut().find(|(c, _)| *c == colour) {
+                    Some((_, total)) => *total += count,
+                    None => self.entries.push((colour, count)),
+                }
+            }
+            self.sample_ends.push(self.entries.len() - first_entry);
+        }
+        self.games.push(GameRecord {
+            id,
+            entries: first_entry..self.entries.len(),
+            samples: first_sample..self.sample_ends.len(),
+        });
+        Ok(())
+    }
+}
+
+/**
+ * Parse `entry`, a slice of `source`, as `<count> <colour>`. The colour
+ * borrows from `entry`.
+ */
+fn parse_entry<'a>(source: &str, entry: &'a str) -> Result<(&'a str, u32), ParseError> {
+    let (count, colour) = split_once(source, entry.trim(), " ")?;
+    let count: u8 = count.parse().map_err(|err: ParseIntError| {
+        let message = match err.kind() {
+            IntErrorKind::PosOverflow => "count does not fit in a u8",
+            _ => "invalid count",
+        };
+        ParseError::new(source, count, message)
+    })?;
+    if colour.is_empty() || !colour.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return Err(ParseError::new(source, colour, "invalid colour"));
+    }
+    Ok((colour, u32::from(count)))
+}
+
+pub fn parse_games(input: &str) -> Result<Games<'_>, ParseError> {
+    let mut games = Games::default();
+    parse_lines(input, |line| games.parse_game(line))?;
+    Ok(games)
+}
+
+/**
+ * How often one colour turns up across a set of games.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColourStats<'a> {
+    pub colour: &'a str,
+    /** Games with at least one cube of the colour. */
+    pub games: usize,
+    /** Cubes of the colour shown over all samples. */
+    pub total: u64,
+    /** Most cubes of the colour shown in one sample. */
+    pub max: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats<'a> {
+    pub games: usize,
+    pub samples: usize,
+    pub colours: Vec<ColourStats<'a>>,
+}
+
+impl<'a> Stats<'a> {
+    fn colour(&mut self, colour: &'a str) -> &mut ColourStats<'a> {
+        let index = match self.colours.iter().position(|c| c.colour == colour) {
+            Some(index) => index,
+            None => {
+                self.colours.push(ColourStats {
+                    colour,
+                    games: 0,
+                    total: 0,
+                    max: 0,
+                });
+                self.colours.len() - 1
+            }
+        };
+        &mut self.colours[index]
+    }
+}
+
+impl fmt::Display for Stats<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} games, {} samples", self.games, self.samples)?;
+        for colour in &self.colours {
+            writeln!(
+                f,
+                "{}: in {} games, {} cubes shown, at most {} at once",
+                colour.colour, colour.games, colour.total, colour.max
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/**
+ * Statistics for every colour seen in `games`, in order of appearance.
+ */
+pub fn stats<'a>(games: &Games<'a>) -> Stats<'a> {
+    let mut stats = Stats {
+        games: games.len(),
+        samples: games.sample_ends.len(),
+        ..Stats::default()
+    };
+    for (game, record) in games.iter().zip(&games.games) {
+        for &(colour, count) in &games.entries[record.entries.clone()] {
+            let entry = stats.colour(colour);
+            entry.total += u64::from(count);
+            entry.max = entry.max.max(count);
+        }
+        for (colour, max) in game.minimal_bag().iter() {
+            if let Some(entry) = stats.colours.iter_mut().find(|c| c.colour == colour) {
+                entry.games += usize::from(max > 0);
+            }
+        }
+    }
+    stats
+}
+
+/**
+ * The bag from the puzzle.
+ */
+pub fn standard_bag() -> Cubes<'static> {
+    Cubes::default()
+        .with("red", 12)
+        .with("green", 13)
+        .with("blue", 14)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+
+    type Input<'a> = Games<'a>;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Games<'_>, ParseError> {
+        parse_games(input)
+    }
+
+    fn part1(games: &Games) -> u32 {
+        let bag = standard_bag();
+        games
+            .iter()
+            .filter(|game| game.is_possible(&bag))
+            .map(|game| game.id)
+            .sum()
+    }
+
+    fn part2(games: &Games) -> u64 {
+        games
+            .iter()
+            .map(|game| game.minimal_bag().power(&["red", "green", "blue"]))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day02::part1(&Day02::parse(SAMPLE).unwrap()), 8);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day02::part2(&Day02::parse(SAMPLE).unwrap()), 2286)
+    }
+
+    #[test]
+    fn test_queries() {
+        let parsed = parse_games(SAMPLE).unwrap();
+        let games: Vec<Game> = parsed.iter().collect();
+        assert_eq!(games.len(), parsed.len());
+        assert_eq!(games[0].minimal_bag().to_string(), "6 blue, 4 red, 2 green");
+        let bag = Cubes::parse("20 red, 13 green, 15 blue").unwrap();
+        let possible: Vec<u32> = games
+            .iter()
+            .filter(|game| game.is_possible(&bag))
+            .map(|game| game.id)
+            .collect();
+        assert_eq!(possible, vec![1, 2, 3, 4, 5]);
+        assert!(!games[0].is_possible(&Cubes::parse("4 red, 2 green").unwrap()));
+
+        let parsed = parse_games("Game 7: 2 teal, 1 red; 3 teal\nGame 8: 1 red").unwrap();
+        let games: Vec<Game> = parsed.iter().collect();
+        let bag = games[0].minimal_bag();
+        assert_eq!(bag.get("teal"), 3);
+        assert_eq!(bag.get("blue"), 0);
+        assert_eq!(bag.power(&["teal", "red"]), 3);
+        assert!(games[1].is_possible(&Cubes::default().with("red", 1)));
+
+        let mut bag = Cubes::parse("2 teal, 1 red").unwrap();
+        assert!(bag.contains(Cubes::parse("1 teal").unwrap().iter()));
+        assert!(!bag.contains([("blue", 1)]));
+        bag.cover(Cubes::parse("1 teal, 3 red, 4 blue").unwrap().iter());
+        assert_eq!(bag.to_string(), "2 teal, 3 red, 4 blue");
+    }
+
+    #[test]
+    fn test_samples() {
+        let parsed = parse_games("Game 4: 1 red, 2 blue, 3 red; 5 green\nGame 5: 1 red").unwrap();
+        let games: Vec<Game> = parsed.iter().collect();
+        let samples: Vec<Sample> = games[0].samples().collect();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(
+            samples[0].iter().collect::<Vec<_>>(),
+            vec![("red", 4), ("blue", 2)]
+        );
+        assert_eq!(
+            games[1].samples().map(Cubes::from).collect::<Vec<_>>(),
+            vec![Cubes::default().with("red", 1)]
+        );
+        assert_eq!(samples[0].get("red"), 4);
+        assert_eq!(samples[1].get("red"), 0);
+        assert_eq!(Cubes::from(samples[0]).to_string(), "4 red, 2 blue");
+        assert!(!games[0].is_possible(&Cubes::parse("3 red, 2 blue, 5 green").unwrap()));
+        assert_eq!(games[0].minimal_bag().to_string(), "4 red, 2 blue, 5 green");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| parse_games(input).unwrap_err().to_string();
+        assert_eq!(
+            error("Game 1: 256 red"),
+            "line 1, column 9: count does not fit in a u8 at \"256\""
+        );
+        assert_eq!(
+            error("Game 1: 3 bl3e"),
+            "line 1, column 11: invalid colour at \"bl3e\""
+        );
+        assert_eq!(
+            error("Game x: 1 red"),
+            "line 1, column 6: invalid number at \"x\""
+        );
+        assert_eq!(
+            error("Game 1 3 blue"),
+            "line 1, column 6: expected \": \" at \"1 3 blue\""
+        );
+        assert_eq!(
+            error("Game 1: 1 red\nGame 2: 1 red; 2"),
+            "line 2, column 16: expected \" \" at \"2\""
+        );
+        assert_eq!(
+            error("Round 1: 1 red"),
+            "line 1, column 1: expected \"Game <id>: <samples>\" at \"Round 1: 1 red\""
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = stats(&parse_games(SAMPLE).unwrap());
+        assert_eq!(
+            stats.to_string(),
+            "5 games, 14 samples
+blue: in 5 games, 50 cubes shown, at most 15 at once
+red: in 5 games, 61 cubes shown, at most 20 at once
+green: in 5 games, 48 cubes shown, at most 13 at once
+"
+        );
+    }
+}
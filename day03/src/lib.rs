@@ -0,0 +1,353 @@
+use common::parse_number;
+use common::ParseError;
+use common::Solution;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+/**
+ * A number in the schematic, spanning columns `start..=end` of its row.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+/**
+ * A symbol in the schematic. `kind` is the text of the symbol, which may be
+ * several characters long, such as an emoji with a skin tone modifier.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol<'a> {
+    pub row: usize,
+    pub column: usize,
+    pub kind: &'a str,
+}
+
+/**
+ * The numbers and symbols of an engine schematic, with the adjacency between
+ * them worked out once. Numbers and symbols are identified by their index in
+ * `numbers()` and `symbols()`, in reading order.
+ *
+ * Columns count characters rather than bytes, with combining marks, variation
+ * selectors, skin tone modifiers and zero-width joined characters sharing the
+ * column of the character they follow.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Schematic<'a> {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol<'a>>,
+    /** Adjacent numbers of each symbol, in reading order. */
+    symbol_numbers: Vec<Vec<usize>>,
+    /** Adjacent symbols of each number, in reading order. */
+    number_symbols: Vec<Vec<usize>>,
+}
+
+/**
+ * Whether `c` extends the character before it instead of starting a column of
+ * its own.
+ */
+fn is_extender(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'       // combining diacritical marks
+        | '\u{1AB0}'..='\u{1AFF}'     // combining diacritical marks extended
+        | '\u{20D0}'..='\u{20FF}'     // combining marks for symbols
+        | '\u{FE00}'..='\u{FE0F}'     // variation selectors
+        | '\u{1F3FB}'..='\u{1F3FF}'   // skin tone modifiers
+        | '\u{E0020}'..='\u{E007F}'   // tag characters
+    )
+}
+
+/**
+ * Length in bytes of the cluster of characters shown in the first column of
+ * `text`.
+ */
+fn cluster_len(text: &str) -> usize {
+    let mut chars = text.char_indices();
+    let mut end = chars.next().map_or(0, |(_, c)| c.len_utf8());
+    let mut joined = false;
+    for (i, c) in chars {
+        if !(joined || is_extender(c) || c == '\u{200D}') {
+            break;
+        }
+        joined = c == '\u{200D}';
+        end = i + c.len_utf8();
+    }
+    end
+}
+
+impl<'a> Schematic<'a> {
+    /**
+     * Parse a schematic in which `.` marks empty space.
+     */
+    pub fn parse(input: &'a str) -> Result<Self, ParseError> {
+        Self::parse_with(input, &['.'])
+    }
+
+    /**
+     * Parse a schematic in which any of `empty` marks empty space. Runs of
+     * ASCII digits are numbers and everything else is a symbol.
+     */
+    pub fn parse_with(input: &'a str, empty: &[char]) -> Result<Self, ParseError> {
+        let mut schematic = Schematic::default();
+        for (row, line) in input.lines().enumerate() {
+            let (mut rest, mut column) = (line, 0);
+            while !rest.is_empty() {
+                let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+                if digits > 0 {
+                    let value = parse_number(line, &rest[..digits])
+                        .map_err(|err| err.within(input, line))?;
+                    schematic.numbers.push(Number {
+                        row,
+                        start: column,
+                        end: column + digits - 1,
+                        value,
+                    });
+                    rest = &rest[digits..];
+                    column += digits;
+                    continue;
+                }
+                let (kind, after) = rest.split_at(cluster_len(rest));
+                let mut chars = kind.chars();
+                let is_empty =
+                    matches!((chars.next(), chars.next()), (Some(c), None) if empty.contains(&c));
+                if !is_empty {
+                    schematic.symbols.push(Symbol { row, column, kind });
+                }
+                rest = after;
+                column += 1;
+            }
+        }
+        schematic.link();
+        Ok(schematic)
+    }
+
+    /**
+     * Fill in the adjacency lists from the positions of numbers and symbols.
+     */
+    fn link(&mut self) {
+        let mut number_at = HashMap::new();
+        for (index, number) in self.numbers.iter().enumerate() {
+            for column in number.start..=number.end {
+                number_at.insert((number.row, column), index);
+            }
+        }
+        self.number_symbols = vec![Vec::new(); self.numbers.len()];
+        self.symbol_numbers = self
+            .symbols
+            .iter()
+            .enumerate()
+            .map(|(index, symbol)| {
+                let mut adjacent: Vec<usize> = (symbol.row.saturating_sub(1)..=symbol.row + 1)
+                    .flat_map(|row| {
+                        (symbol.column.saturating_sub(1)..=symbol.column + 1)
+                            .map(move |column| (row, column))
+                    })
+                    .filter_map(|position| number_at.get(&position).copied())
+                    .collect();
+                adjacent.sort();
+                adjacent.dedup();
+                for &number in &adjacent {
+                    self.number_symbols[number].push(index);
+                }
+                adjacent
+            })
+            .collect();
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol<'a>] {
+        &self.symbols
+    }
+
+    /**
+     * Numbers touching symbol `symbol`, diagonals included.
+     */
+    pub fn numbers_adjacent_to(&self, symbol: usize) -> impl Iterator<Item = &Number> {
+        self.symbol_numbers[symbol]
+            .iter()
+            .map(|&number| &self.numbers[number])
+    }
+
+    /**
+     * Symbols touching number `number`, diagonals included.
+     */
+    pub fn symbols_adjacent_to(&self, number: usize) -> impl Iterator<Item = &Symbol<'a>> {
+        self.number_symbols[number]
+            .iter()
+            .map(|&symbol| &self.symbols[symbol])
+    }
+
+    /**
+     * Numbers touching at least one symbol.
+     */
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    /**
+     * Indices of the symbols touching exactly `parts` numbers, optionally
+     * only those of one kind.
+     */
+    pub fn gears<'q>(
+        &'q self,
+        kind: Option<&'q str>,
+        parts: usize,
+    ) -> impl Iterator<Item = usize> + 'q {
+        (0..self.symbols.len()).filter(move |&symbol| {
+            kind.is_none_or(|kind| self.symbols[symbol].kind == kind)
+                && self.symbol_numbers[symbol].len() == parts
+        })
+    }
+
+    /**
+     * Product of the numbers touching symbol `symbol`.
+     */
+    pub fn ratio(&self, symbol: usize) -> u64 {
+        self.numbers_adjacent_to(symbol)
+            .map(|number| u64::from(number.value))
+            .product()
+    }
+
+    /**
+     * Sum of the ratios of the symbols touching exactly `parts` numbers, for
+     * each kind of symbol.
+     */
+    pub fn ratios_by_kind(&self, parts: usize) -> BTreeMap<&'a str, u64> {
+        let mut ratios = BTreeMap::new();
+        for symbol in self.gears(None, parts) {
+            *ratios.entry(self.symbols[symbol].kind).or_default() += self.ratio(symbol);
+        }
+        ratios
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+
+    type Input<'a> = Schematic<'a>;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Schematic<'_>, ParseError> {
+        Schematic::parse(input)
+    }
+
+    fn part1(schematic: &Schematic) -> u32 {
+        schematic.part_numbers().map(|number| number.value).sum()
+    }
+
+    fn part2(schematic: &Schematic) -> u64 {
+        schematic.ratios_by_kind(2).values().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day03::part1(&Day03::parse(SAMPLE).unwrap()), 4361)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day03::part2(&Day03::parse(SAMPLE).unwrap()), 467835)
+    }
+
+    #[test]
+    fn test_queries() {
+        let schematic = Schematic::parse(SAMPLE).unwrap();
+        let values = |numbers: Vec<&Number>| -> Vec<u32> {
+            numbers.iter().map(|number| number.value).collect()
+        };
+        // The '*' on the second row.
+        assert_eq!(
+            values(schematic.numbers_adjacent_to(0).collect()),
+            vec![467, 35]
+        );
+        let kinds: Vec<&str> = schematic.symbols_adjacent_to(2).map(|s| s.kind).collect();
+        assert_eq!(kinds, vec!["*"]);
+        assert_eq!(schematic.symbols_adjacent_to(1).count(), 0);
+        let gears: Vec<usize> = schematic.gears(Some("*"), 2).collect();
+        assert_eq!(gears, vec![0, 5]);
+        assert_eq!(schematic.gears(Some("*"), 1).count(), 1);
+        assert_eq!(schematic.gears(None, 1).count(), 4);
+        assert_eq!(schematic.ratio(5), 755 * 598);
+        assert_eq!(
+            schematic.ratios_by_kind(1),
+            BTreeMap::from([("#", 633), ("$", 664), ("*", 617), ("+", 592)])
+        );
+    }
+
+    #[test]
+    fn test_unicode_symbols() {
+        // Multi-byte symbols take one column, so 4 and 5 touch the arrow.
+        let schematic = Schematic::parse("12€..4\n....→.\n.....5").unwrap();
+        let symbols: Vec<(usize, &str)> = schematic
+            .symbols()
+            .iter()
+            .map(|symbol| (symbol.column, symbol.kind))
+            .collect();
+        assert_eq!(symbols, vec![(2, "€"), (4, "→")]);
+        assert_eq!(Day03::part1(&schematic), 21);
+        assert_eq!(schematic.ratios_by_kind(2), BTreeMap::from([("→", 20)]));
+
+        // A thumbs up with a skin tone, and a family joined with zero-width
+        // joiners, are one column each.
+        let schematic = Schematic::parse("👍🏽7.\n👨\u{200D}👩\u{200D}👧.3").unwrap();
+        let symbols: Vec<(usize, usize, &str)> = schematic
+            .symbols()
+            .iter()
+            .map(|symbol| (symbol.row, symbol.column, symbol.kind))
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![(0, 0, "👍🏽"), (1, 0, "👨\u{200D}👩\u{200D}👧")]
+        );
+        assert_eq!(schematic.numbers()[1].start, 2);
+        assert_eq!(schematic.gears(Some("👍🏽"), 1).count(), 1);
+        assert_eq!(schematic.symbols_adjacent_to(1).count(), 0);
+        // A combining accent belongs to the character before it.
+        let schematic = Schematic::parse("e\u{301}1").unwrap();
+        assert_eq!(schematic.symbols()[0].kind, "e\u{301}");
+        assert_eq!(schematic.numbers()[0].start, 1);
+    }
+
+    #[test]
+    fn test_empty_characters() {
+        let schematic = Schematic::parse_with("1 ·\n·*·\n  2", &[' ', '·']).unwrap();
+        assert_eq!(schematic.symbols().len(), 1);
+        assert_eq!(Day03::part2(&schematic), 2);
+        // Without configuring them, spaces are symbols.
+        let schematic = Schematic::parse("1 .\n.*.\n  2").unwrap();
+        assert_eq!(schematic.symbols().len(), 4);
+        assert_eq!(
+            Schematic::parse("..\n99999999999").unwrap_err().to_string(),
+            "line 2, column 1: invalid number at \"99999999999\""
+        );
+    }
+}
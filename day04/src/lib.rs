@@ -0,0 +1,333 @@
+use common::parse_lines;
+use common::parse_number;
+use common::split_once;
+use common::ParseError;
+use common::Solution;
+use std::fmt::Write;
+use std::ops::Range;
+
+/**
+ * A set of card numbers stored as a bitset, so two sets are compared a word
+ * at a time.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NumberSet {
+    words: Vec<u64>,
+}
+
+impl NumberSet {
+    pub fn insert(&mut self, number: u16) {
+        let (word, bit) = (usize::from(number) / 64, number % 64);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << bit;
+    }
+
+    pub fn contains(&self, number: u16) -> bool {
+        let (word, bit) = (usize::from(number) / 64, number % 64);
+        self.words
+            .get(word)
+            .is_some_and(|word| word & (1 << bit) != 0)
+    }
+
+    /**
+     * Number of numbers in both sets.
+     */
+    pub fn common(&self, other: &NumberSet) -> u32 {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones())
+            .sum()
+    }
+}
+
+impl FromIterator<u16> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = u16>>(numbers: I) -> Self {
+        let mut set = NumberSet::default();
+        for number in numbers {
+            set.insert(number);
+        }
+        set
+    }
+}
+
+/**
+ * A scratchcard's winning numbers and the numbers you have. Numbers repeated
+ * on one side count once.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: NumberSet,
+    pub have: NumberSet,
+}
+
+impl Card {
+    pub fn matches(&self) -> usize {
+        self.winning.common(&self.have) as usize
+    }
+
+    pub fn points(&self) -> u32 {
+        match self.matches() {
+            0 => 0,
+            n => 1 << (n - 1),
+        }
+    }
+}
+
+fn process_numbers(line: &str, s: &str) -> Result<NumberSet, ParseError> {
+    s.split_whitespace()
+        .map(|number| parse_number(line, number))
+        .collect()
+}
+
+fn process_line(line: &str) -> Result<Card, ParseError> {
+    let (card_part, numbers_part) = split_once(line, line, ":")?;
+    let id = card_part
+        .strip_prefix("Card")
+        .ok_or_else(|| ParseError::new(line, card_part, "expected \"Card <id>\""))?;
+    let id = parse_number(line, id.trim())?;
+    let (winning_part, have_part) = split_once(line, numbers_part, "|")?;
+    Ok(Card {
+        id,
+        winning: process_numbers(line, winning_part)?,
+        have: process_numbers(line, have_part)?,
+    })
+}
+
+/**
+ * The copies won by one card: every copy of it adds one copy of each card in
+ * `targets`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Win {
+    pub card: usize,
+    pub copies: u64,
+    pub targets: Range<usize>,
+    /** Matches pointing past the last card, which win nothing. */
+    pub past_end: usize,
+}
+
+/**
+ * How copies spread through a pile of cards, in card order.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cascade {
+    pub copies: Vec<u64>,
+    pub wins: Vec<Win>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcards {
+    pub cards: Vec<Card>,
+}
+
+impl Scratchcards {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Scratchcards {
+            cards: parse_lines(input, process_line)?,
+        })
+    }
+
+    pub fn points(&self) -> u32 {
+        self.cards.iter().map(Card::points).sum()
+    }
+
+    /**
+     * Play the pile: each card wins copies of the cards after it, one per
+     * match, for every copy of it held. Wins running past the last card are
+     * dropped.
+     */
+    pub fn cascade(&self) -> Cascade {
+        let mut copies = vec![1; self.cards.len()];
+        let mut wins = Vec::new();
+        for (index, card) in self.cards.iter().enumerate() {
+            let matches = card.matches();
+            let targets = index + 1..(index + 1 + matches).min(self.cards.len());
+            for target in targets.clone() {
+                copies[target] += copies[index];
+            }
+            if matches > 0 {
+                wins.push(Win {
+                    card: index,
+                    copies: copies[index],
+                    past_end: matches - targets.len(),
+                    targets,
+                });
+            }
+        }
+        Cascade { copies, wins }
+    }
+
+    pub fn total_copies(&self) -> u64 {
+        self.cascade().copies.iter().sum()
+    }
+
+    /**
+     * One row per card: its copies, matches, and the copies it adds.
+     */
+    pub fn render_table(&self, cascade: &Cascade) -> String {
+        let mut output = String::from(" card    copies  matches  gives\n");
+        let mut wins = cascade.wins.iter().peekable();
+        for (index, card) in self.cards.iter().enumerate() {
+            let gives = match wins.next_if(|win| win.card == index) {
+                Some(win) => {
+                    let Range { start, end } = win.targets;
+                    let mut gives = Vec::new();
+                    match end - start {
+                        0 => {}
+                        1 => {
+                            gives.push(format!("+{} to card {}", win.copies, self.cards[start].id))
+                        }
+                        _ => gives.push(format!(
+                            "+{} to cards {}-{}",
+                            win.copies,
+                            self.cards[start].id,
+                            self.cards[end - 1].id
+                        )),
+                    }
+                    if win.past_end > 0 {
+                        gives.push(format!("{} past the end", win.past_end));
+                    }
+                    gives.join(", ")
+                }
+                None => "-".to_string(),
+            };
+            writeln!(
+                output,
+                "{:>5} {:>9} {:>8}  {gives}",
+                card.id,
+                cascade.copies[index],
+                card.matches()
+            )
+            .expect("writing to a String");
+        }
+        output
+    }
+
+    /**
+     * The cascade as a Graphviz digraph, with an edge from each card to every
+     * card it adds copies of.
+     */
+    pub fn render_dot(&self, cascade: &Cascade) -> String {
+        let mut output = String::from("digraph cascade {\n");
+        for (card, copies) in self.cards.iter().zip(&cascade.copies) {
+            writeln!(
+                output,
+                "  {} [label=\"Card {}\\n{copies} copies\"];",
+                card.id, card.id
+            )
+            .expect("writing to a String");
+        }
+        for win in &cascade.wins {
+            for target in win.targets.clone() {
+                writeln!(
+                    output,
+                    "  {} -> {} [label=\"{}\"];",
+                    self.cards[win.card].id, self.cards[target].id, win.copies
+                )
+                .expect("writing to a String");
+            }
+        }
+        output += "}\n";
+        output
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+
+    type Input<'a> = Scratchcards;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Scratchcards, ParseError> {
+        Scratchcards::parse(input)
+    }
+
+    fn part1(cards: &Scratchcards) -> u32 {
+        cards.points()
+    }
+
+    fn part2(cards: &Scratchcards) -> u64 {
+        cards.total_copies()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day04::part1(&Day04::parse(SAMPLE).unwrap()), 13)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day04::part2(&Day04::parse(SAMPLE).unwrap()), 30)
+    }
+
+    #[test]
+    fn test_number_set() {
+        let a: NumberSet = [3, 64, 200].into_iter().collect();
+        let b: NumberSet = [64, 3, 7].into_iter().collect();
+        assert!(a.contains(200) && !a.contains(7) && !a.contains(1000));
+        assert_eq!(a.common(&b), 2);
+        assert_eq!(b.common(&a), 2);
+        assert_eq!(a.common(&NumberSet::default()), 0);
+    }
+
+    #[test]
+    fn test_cascade() {
+        let cards = Scratchcards::parse(SAMPLE).unwrap();
+        let cascade = cards.cascade();
+        assert_eq!(cascade.copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(
+            cards.render_table(&cascade),
+            " card    copies  matches  gives
+    1         1        4  +1 to cards 2-5
+    2         2        2  +2 to cards 3-4
+    3         4        2  +4 to cards 4-5
+    4         8        1  +8 to card 5
+    5        14        0  -
+    6         1        0  -
+"
+        );
+        // The last card's win runs past the end instead of out of bounds.
+        let cards = Scratchcards::parse("Card 1: 1 2 | 1\nCard 2: 1 2 | 1 2 3").unwrap();
+        let cascade = cards.cascade();
+        assert_eq!(cascade.copies, vec![1, 2]);
+        assert_eq!(
+            cards.render_dot(&cascade),
+            "digraph cascade {
+  1 [label=\"Card 1\\n1 copies\"];
+  2 [label=\"Card 2\\n2 copies\"];
+  1 -> 2 [label=\"1\"];
+}
+"
+        );
+        assert!(cards
+            .render_table(&cascade)
+            .ends_with("    2         2        2  2 past the end\n"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Day04::parse("Card 1: 1 | 70000").unwrap_err().to_string(),
+            "line 1, column 13: invalid number at \"70000\""
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::ops::RangeInclusive;
+
+/**
+ * Maps `start..=end` to `start + offset..=end + offset`.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Piece {
+    pub start: u64,
+    pub end: u64,
+    pub offset: i128,
+}
+
+impl Piece {
+    fn apply(&self, value: u64) -> u64 {
+        (i128::from(value) + self.offset) as u64
+    }
+
+    fn image(&self) -> RangeInclusive<u64> {
+        self.apply(self.start)..=self.apply(self.end)
+    }
+}
+
+/**
+ * A piecewise-linear map over all of `u64`, stored as sorted pieces that
+ * cover every value. Values not covered by any rule map to themselves.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalMap {
+    pieces: Vec<Piece>,
+}
+
+impl Default for IntervalMap {
+    fn default() -> Self {
+        IntervalMap::identity()
+    }
+}
+
+impl IntervalMap {
+    pub fn identity() -> Self {
+        IntervalMap {
+            pieces: vec![Piece {
+                start: 0,
+                end: u64::MAX,
+                offset: 0,
+            }],
+        }
+    }
+
+    /**
+     * A map from almanac rules `(destination start, source start, length)`.
+     * Where source ranges overlap the earlier rule wins. A rule stops where
+     * its source or destination range reaches `u64::MAX`.
+     */
+    pub fn from_rules(rules: impl IntoIterator<Item = (u64, u64, u64)>) -> Self {
+        let rules: Vec<Piece> = rules
+            .into_iter()
+            .filter(|&(_, _, len)| len > 0)
+            .map(|(dst, src, len)| Piece {
+                start: src,
+                end: src.saturating_add((len - 1).min(u64::MAX - dst)),
+                offset: i128::from(dst) - i128::from(src),
+            })
+            .collect();
+        let mut bounds: Vec<u64> = rules
+            .iter()
+            .flat_map(|rule| [Some(rule.start), rule.end.checked_add(1)])
+            .flatten()
+            .chain([0])
+            .collect();
+        bounds.sort();
+        bounds.dedup();
+        let pieces = bounds
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let end = bounds.get(i + 1).map_or(u64::MAX, |next| next - 1);
+                let offset = rules
+                    .iter()
+                    .find(|rule| rule.start <= start && start <= rule.end)
+                    .map_or(0, |rule| rule.offset);
+                Piece { start, end, offset }
+            })
+            .collect();
+        IntervalMap::normalized(pieces)
+    }
+
+    /**
+     * Merge neighbouring pieces that shift by the same offset.
+     */
+    fn normalized(pieces: Vec<Piece>) -> Self {
+        let mut merged: Vec<Piece> = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            match merged.last_mut() {
+                Some(last) if last.offset == piece.offset && last.end + 1 == piece.start => {
+                    last.end = piece.end
+                }
+                _ => merged.push(piece),
+            }
+        }
+        IntervalMap { pieces: merged }
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    fn piece(&self, value: u64) -> &Piece {
+        &self.pieces[self.pieces.partition_point(|piece| piece.end < value)]
+    }
+
+    pub fn apply(&self, value: u64) -> u64 {
+        self.piece(value).apply(value)
+    }
+
+    /**
+     * The map applying `self` and then `next`, as a single map.
+     */
+    pub fn then(&self, next: &IntervalMap) -> IntervalMap {
+        let mut pieces = Vec::new();
+        for piece in &self.pieces {
+            let image = piece.image();
+            let first = next.pieces.partition_point(|p| p.end < *image.start());
+            for later in next.pieces[first..]
+                .iter()
+                .take_while(|p| p.start <= *image.end())
+            {
+                let start = (*image.start()).max(later.start);
+                let end = (*image.end()).min(later.end);
+                let back = |value: u64| (i128::from(value) - piece.offset) as u64;
+                pieces.push(Piece {
+                    start: back(start),
+                    end: back(end),
+                    offset: piece.offset + later.offset,
+                });
+            }
+        }
+        IntervalMap::normalized(pieces)
+    }
+
+    /**
+     * The inverse map, or None if two values map to the same one.
+     */
+    pub fn inverse(&self) -> Option<IntervalMap> {
+        let mut pieces: Vec<Piece> = self
+            .pieces
+            .iter()
+            .map(|piece| {
+                let image = piece.image();
+                Piece {
+                    start: *image.start(),
+                    end: *image.end(),
+                    offset: -piece.offset,
+                }
+            })
+            .collect();
+        pieces.sort_by_key(|piece| piece.start);
+        // The images have the same total size as the domain, so if they do
+        // not overlap they cover it.
+        let disjoint = pieces.windows(2).all(|w| w[0].end < w[1].start);
+        disjoint.then(|| IntervalMap::normalized(pieces))
+    }
+
+    /**
+     * Every value that maps to `value`, in increasing order.
+     */
+    pub fn preimage(&self, value: u64) -> Vec<u64> {
+        self.pieces
+            .iter()
+            .filter(|piece| piece.image().contains(&value))
+            .map(|piece| (i128::from(value) - piece.offset) as u64)
+            .collect()
+    }
+
+    /**
+     * Smallest value any of `ranges` maps to, or None if they are all empty.
+     */
+    pub fn min_image(&self, ranges: &[RangeInclusive<u64>]) -> Option<u64> {
+        ranges
+            .iter()
+            .filter(|range| !range.is_empty())
+            .flat_map(|range| {
+                let first = self.pieces.partition_point(|p| p.end < *range.start());
+                self.pieces[first..]
+                    .iter()
+                    .take_while(|p| p.start <= *range.end())
+                    .map(|piece| piece.apply(piece.start.max(*range.start())))
+            })
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_rules() {
+        let map = IntervalMap::from_rules([(50, 98, 2), (52, 50, 48)]);
+        assert_eq!(map.apply(98), 50);
+        assert_eq!(map.apply(99), 51);
+        assert_eq!(map.apply(100), 100);
+        assert_eq!(map.apply(50), 52);
+        assert_eq!(map.apply(49), 49);
+        assert_eq!(map.apply(u64::MAX), u64::MAX);
+        assert_eq!(map.pieces().len(), 4);
+        // The first of two overlapping rules wins.
+        let map = IntervalMap::from_rules([(100, 10, 5), (200, 12, 5)]);
+        assert_eq!(
+            (map.apply(12), map.apply(15), map.apply(17)),
+            (102, 203, 17)
+        );
+        // Destinations stop at u64::MAX, and the values past them map to
+        // themselves.
+        let map = IntervalMap::from_rules([(u64::MAX - 1, 0, 5)]);
+        assert_eq!((map.apply(1), map.apply(2), map.apply(4)), (u64::MAX, 2, 4));
+        let map = IntervalMap::from_rules([(0, u64::MAX - 1, 5)]);
+        assert_eq!((map.apply(u64::MAX - 1), map.apply(u64::MAX)), (0, 1));
+        // Rules that shift by the same amount merge.
+        let map = IntervalMap::from_rules([(20, 10, 5), (25, 15, 5)]);
+        assert_eq!(map.pieces().len(), 3);
+    }
+
+    #[test]
+    fn test_then() {
+        let first = IntervalMap::from_rules([(50, 98, 2), (52, 50, 48)]);
+        let second = IntervalMap::from_rules([(0, 15, 37), (37, 52, 2), (39, 0, 15)]);
+        let composed = first.then(&second);
+        for value in 0..200 {
+            assert_eq!(composed.apply(value), second.apply(first.apply(value)));
+        }
+        assert_eq!(IntervalMap::identity().then(&first), first);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let map = IntervalMap::from_rules([(50, 98, 2), (52, 50, 48)]);
+        let inverse = map.inverse().unwrap();
+        for value in 0..200 {
+            assert_eq!(inverse.apply(map.apply(value)), value);
+        }
+        // 10 and 0 both map to 0.
+        let map = IntervalMap::from_rules([(0, 10, 1)]);
+        assert_eq!(map.inverse(), None);
+        assert_eq!(map.preimage(0), vec![0, 10]);
+        assert_eq!(map.preimage(10), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_min_image() {
+        let map = IntervalMap::from_rules([(50, 98, 2), (52, 50, 48)]);
+        assert_eq!(map.min_image(&[79..=92, 55..=67]), Some(57));
+        assert_eq!(map.min_image(&[90..=99]), Some(50));
+        assert_eq!(map.min_image(&[]), None);
+    }
+}
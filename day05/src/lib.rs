@@ -0,0 +1,462 @@
+pub mod interval_map;
+
+use common::parse_number;
+use common::split_once;
+use common::MaybeAnswer;
+use common::ParseError;
+use common::Solution;
+use interval_map::IntervalMap;
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Eq, PartialEq)]
+struct Mapping {
+    dst_start: u64,
+    src_start: u64,
+    len: u64,
+}
+
+fn parse_mapping(mapping_line: &str) -> Result<Mapping, ParseError> {
+    let numbers: Vec<u64> = mapping_line
+        .split_whitespace()
+        .map(|num_str| parse_number(mapping_line, num_str))
+        .collect::<Result<_, _>>()?;
+    if numbers.len() != 3 {
+        return Err(ParseError::new(
+            mapping_line,
+            mapping_line,
+            "expected 3 numbers",
+        ));
+    }
+    let mapping = Mapping {
+        dst_start: numbers[0],
+        src_start: numbers[1],
+        len: numbers[2],
+    };
+    if range_end(mapping.src_start, mapping.len).is_none()
+        || range_end(mapping.dst_start, mapping.len).is_none()
+    {
+        return Err(ParseError::new(
+            mapping_line,
+            mapping_line,
+            "range runs past u64::MAX",
+        ));
+    }
+    Ok(mapping)
+}
+
+fn parse_map(map_lines: &str) -> Result<Vec<Mapping>, ParseError> {
+    map_lines
+        .lines()
+        .skip(1)
+        .map(|line| parse_mapping(line).map_err(|err| err.within(map_lines, line)))
+        .collect()
+}
+
+/**
+ * Source and destination categories named by a map's header line, such as
+ * `seed-to-soil map:`.
+ */
+fn parse_header(line: &str) -> Result<(&str, &str), ParseError> {
+    let name = line.strip_suffix(" map:").ok_or_else(|| {
+        ParseError::new(line, line, "expected \"<source>-to-<destination> map:\"")
+    })?;
+    split_once(line, name, "-to-")
+}
+
+/**
+ * Last value of a range of `len` values from `start`, or None if it is past
+ * `u64::MAX`.
+ */
+fn range_end(start: u64, len: u64) -> Option<u64> {
+    start.checked_add(len.saturating_sub(1))
+}
+
+/**
+ * Every problem with the maps of an almanac: rules that do not parse, such
+ * as those whose ranges run past `u64::MAX`, headers that do not chain on
+ * from `seed` to `location`, source ranges that overlap within a map (the
+ * parser lets the earlier rule win) and gaps between a map's source ranges
+ * (values there map to themselves). Only the first of these stops the
+ * parser.
+ */
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    let mut category = "seed";
+    for paragraph in input.split("\n\n").skip(1) {
+        let mut lines = paragraph.lines();
+        let header = lines.next().unwrap_or(paragraph);
+        match parse_header(header) {
+            Ok((source, destination)) => {
+                if source != category {
+                    errors.push(ParseError::new(
+                        input,
+                        source,
+                        format!("expected a map from {category}"),
+                    ));
+                }
+                category = destination;
+            }
+            Err(err) => errors.push(err.within(input, header)),
+        }
+        let mut sources: Vec<(u64, u64, &str)> = Vec::new();
+        for line in lines {
+            let mapping = match parse_mapping(line) {
+                Ok(mapping) => mapping,
+                Err(err) => {
+                    errors.push(err.within(input, line));
+                    continue;
+                }
+            };
+            if mapping.len == 0 {
+                continue;
+            }
+            let src_end = mapping.src_start + (mapping.len - 1);
+            if let Some((_, _, earlier)) = sources
+                .iter()
+                .find(|&&(start, end, _)| mapping.src_start <= end && start <= src_end)
+            {
+                errors.push(ParseError::new(
+                    input,
+                    line,
+                    format!("source range overlaps {earlier:?}"),
+                ));
+            }
+            sources.push((mapping.src_start, src_end, line));
+        }
+        sources.sort();
+        let mut covered = None;
+        for &(start, end, line) in &sources {
+            let next = covered.and_then(|covered: u64| covered.checked_add(1));
+            if let Some(next) = next.filter(|&next| next < start) {
+                errors.push(ParseError::new(
+                    input,
+                    line,
+                    format!(
+                        "gap before this rule: {next}..={} map to themselves",
+                        start - 1
+                    ),
+                ));
+            }
+            covered = covered.max(Some(end));
+        }
+    }
+    if category != "location" {
+        errors.push(ParseError::new(
+            input,
+            &input[input.len()..],
+            format!("expected the maps to end at location, not {category}"),
+        ));
+    }
+    errors
+}
+
+fn parse_seeds(line: &str) -> Result<Vec<u64>, ParseError> {
+    line.split_whitespace()
+        .skip(1) // Skip the 'seeds:' heading
+        .map(|num_str| parse_number(line, num_str))
+        .collect()
+}
+
+pub struct Almanac {
+    seeds: Vec<u64>,
+    /** Every map composed into one, from seed to location. */
+    map: IntervalMap,
+}
+
+impl Almanac {
+    /**
+     * The map from seed to location.
+     */
+    pub fn map(&self) -> &IntervalMap {
+        &self.map
+    }
+
+    /**
+     * Every seed number planted at `location`.
+     */
+    pub fn seeds_for_location(&self, location: u64) -> Vec<u64> {
+        self.map.preimage(location)
+    }
+}
+
+fn interval_map(map: &[Mapping]) -> IntervalMap {
+    IntervalMap::from_rules(
+        map.iter()
+            .map(|mapping| (mapping.dst_start, mapping.src_start, mapping.len)),
+    )
+}
+
+fn parse_almanac(input: &str) -> Result<Almanac, ParseError> {
+    let paragraphs: Vec<&str> = input.split("\n\n").collect();
+    let seeds: Vec<u64> = parse_seeds(paragraphs[0])?;
+    let maps: Vec<Vec<Mapping>> = paragraphs
+        .iter()
+        .skip(1)
+        .map(|map_lines| parse_map(map_lines).map_err(|err| err.within(input, map_lines)))
+        .collect::<Result<_, _>>()?;
+    let map = maps.iter().fold(IntervalMap::identity(), |map, next| {
+        map.then(&interval_map(next))
+    });
+    Ok(Almanac { seeds, map })
+}
+
+/**
+ * The seed numbers as `(start, length)` pairs. Empty ranges are skipped, as
+ * `IntervalMap::from_rules` skips empty rules, and ranges that run past
+ * `u64::MAX` stop there.
+ */
+fn seed_ranges(numbers: &[u64]) -> Vec<RangeInclusive<u64>> {
+    numbers
+        .chunks_exact(2)
+        .filter(|pair| pair[1] > 0)
+        .map(|pair| pair[0]..=pair[0].saturating_add(pair[1] - 1))
+        .collect()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+
+    type Input<'a> = Almanac;
+    type Part1 = MaybeAnswer<u64>;
+    type Part2 = MaybeAnswer<u64>;
+
+    fn parse(input: &str) -> Result<Almanac, ParseError> {
+        parse_almanac(input)
+    }
+
+    fn part1(almanac: &Almanac) -> MaybeAnswer<u64> {
+        let seeds: Vec<_> = almanac.seeds.iter().map(|&seed| seed..=seed).collect();
+        MaybeAnswer(almanac.map.min_image(&seeds))
+    }
+
+    fn part2(almanac: &Almanac) -> MaybeAnswer<u64> {
+        MaybeAnswer(almanac.map.min_image(&seed_ranges(&almanac.seeds)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_seeds() {
+        assert_eq!(parse_seeds("seeds: 79 14 55 13"), Ok(vec!(79, 14, 55, 13)))
+    }
+
+    #[test]
+    fn test_parse_mapping() {
+        let expected = Mapping {
+            dst_start: 50,
+            src_start: 98,
+            len: 2,
+        };
+        assert_eq!(parse_mapping("50 98 2"), Ok(expected))
+    }
+
+    #[test]
+    fn test_parse_map() {
+        let expected = vec![
+            Mapping {
+                dst_start: 50,
+                src_start: 98,
+                len: 2,
+            },
+            Mapping {
+                dst_start: 52,
+                src_start: 50,
+                len: 48,
+            },
+        ];
+        assert_eq!(
+            parse_map(
+                "seed-to-soil map:
+50 98 2
+52 50 48"
+            ),
+            Ok(expected)
+        )
+    }
+
+    #[test]
+    fn test_parse_almanac_error() {
+        let err = parse_almanac(
+            "seeds: 79 14
+
+seed-to-soil map:
+50 98 2
+52 50 4x8",
+        )
+        .err()
+        .unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (5, 7, "4x8"));
+    }
+
+    #[test]
+    fn test_interval_map() {
+        let map = vec![
+            Mapping {
+                dst_start: 50,
+                src_start: 98,
+                len: 2,
+            },
+            Mapping {
+                dst_start: 52,
+                src_start: 50,
+                len: 48,
+            },
+        ];
+        let map = interval_map(&map);
+        assert_eq!(map.apply(98), 50);
+        assert_eq!(map.apply(99), 51);
+        assert_eq!(map.apply(100), 100);
+        assert_eq!(map.apply(50), 52);
+        assert_eq!(map.apply(51), 53);
+        assert_eq!(map.apply(50 + 47), 52 + 47);
+        // A range can span several rules.
+        assert_eq!(map.min_image(&[79..=92, 55..=67]), Some(57));
+        assert_eq!(map.min_image(&[60..=200]), Some(50));
+    }
+
+    static SAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day05::part1(&Day05::parse(SAMPLE).unwrap()),
+            MaybeAnswer(Some(35))
+        )
+    }
+
+    #[test]
+    fn test_seed_ranges() {
+        assert_eq!(
+            seed_ranges(&[79, 14, 55, 13, 5, 0, u64::MAX - 1, 10]),
+            vec![79..=92, 55..=67, u64::MAX - 1..=u64::MAX]
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day05::part2(&Day05::parse(SAMPLE).unwrap()),
+            MaybeAnswer(Some(46))
+        );
+        // Without seeds there is no lowest location.
+        let almanac = Day05::parse("seeds:\n\nseed-to-soil map:\n1 2 3").unwrap();
+        assert_eq!(Day05::part1(&almanac), MaybeAnswer(None));
+        assert_eq!(Day05::part2(&almanac), MaybeAnswer(None));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(validate(SAMPLE), vec![]);
+        let input = "seeds: 1 2
+
+seed-to-soil map:
+50 98 2
+52 90 10
+0 10 5
+
+fertilizer-to-water map:
+0 18446744073709551615 2
+
+water-to-light map
+1 2 3";
+        let errors: Vec<String> = validate(input).iter().map(|err| err.to_string()).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "line 5, column 1: source range overlaps \"50 98 2\" at \"52 90 10\"",
+                "line 5, column 1: gap before this rule: 15..=89 map to themselves at \"52 90 10\"",
+                "line 8, column 1: expected a map from soil at \"fertilizer\"",
+                "line 9, column 1: range runs past u64::MAX at \"0 18446744073709551615 2\"",
+                "line 11, column 1: expected \"<source>-to-<destination> map:\" at \"water-to-light map\"",
+                "line 12, column 6: expected the maps to end at location, not water",
+            ]
+        );
+        // Overflow is the one problem that stops the parser.
+        assert_eq!(Day05::parse(input).err().unwrap().to_string(), errors[3]);
+        let err = Day05::parse("seeds: 4\n\nseed-to-soil map:\n18446744073709551614 0 5");
+        assert_eq!(
+            err.err().unwrap().to_string(),
+            "line 4, column 1: range runs past u64::MAX at \"18446744073709551614 0 5\""
+        );
+        let errors: Vec<String> = validate("seeds: 1\n\nsoil-to-location map:\n1 2 3")
+            .iter()
+            .map(|err| err.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec!["line 3, column 1: expected a map from seed at \"soil\""]
+        );
+    }
+
+    #[test]
+    fn test_parse_unvalidated() {
+        // Validation only reports problems: the earlier of two overlapping
+        // rules wins, and maps are applied in the order they appear.
+        let almanac = Day05::parse(
+            "seeds: 95 98\n\nseed-to-soil map:\n50 98 2\n52 90 10\n\nfoo map:\n0 57 1",
+        )
+        .unwrap();
+        assert_eq!(almanac.map().apply(98), 50);
+        assert_eq!(almanac.map().apply(95), 0);
+        assert_eq!(Day05::part1(&almanac), MaybeAnswer(Some(0)));
+    }
+
+    #[test]
+    fn test_composed_map() {
+        let almanac = Day05::parse(SAMPLE).unwrap();
+        let maps: Vec<IntervalMap> = SAMPLE
+            .split("\n\n")
+            .skip(1)
+            .map(|map| interval_map(&parse_map(map).unwrap()))
+            .collect();
+        for seed in 0..120 {
+            let location = maps.iter().fold(seed, |num, map| map.apply(num));
+            assert_eq!(almanac.map().apply(seed), location);
+            assert!(almanac.seeds_for_location(location).contains(&seed));
+        }
+        let lowest = seed_ranges(&almanac.seeds)
+            .into_iter()
+            .flatten()
+            .map(|seed| almanac.map().apply(seed))
+            .min();
+        assert_eq!(MaybeAnswer(lowest), Day05::part2(&almanac));
+        assert_eq!(almanac.seeds_for_location(46), vec![82]);
+    }
+}
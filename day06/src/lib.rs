@@ -0,0 +1,290 @@
+use common::parse_lines;
+use common::parse_number;
+use common::split_once;
+use common::ParseError;
+use common::Solution;
+use num_bigint::BigUint;
+use std::ops::RangeInclusive;
+
+/**
+ * A race lasting `time` milliseconds. Holding the button for `h` of them
+ * travels `h * (time - h)` millimetres, which wins if it beats `record`.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    pub record: u64,
+}
+
+impl Race {
+    fn wins(&self, hold: u64) -> bool {
+        let (hold, time) = (u128::from(hold), u128::from(self.time));
+        hold * (time - hold) > u128::from(self.record)
+    }
+
+    /**
+     * Every hold time that beats the record, or None if none does.
+     *
+     * The winning holds lie strictly between the roots of
+     * `h^2 - time * h + record`, found with an integer square root and then
+     * nudged onto the exact boundary, so no precision is lost.
+     */
+    pub fn winning_holds(&self) -> Option<RangeInclusive<u64>> {
+        let time = u128::from(self.time);
+        let discriminant = (time * time).checked_sub(4 * u128::from(self.record))?;
+        let mut low = ((time - discriminant.isqrt()) / 2) as u64;
+        let middle = self.time / 2;
+        while low <= middle && !self.wins(low) {
+            low += 1;
+        }
+        while low > 0 && self.wins(low - 1) {
+            low -= 1;
+        }
+        (low <= middle && self.wins(low)).then(|| low..=self.time - low)
+    }
+
+    pub fn ways_to_win(&self) -> u64 {
+        self.winning_holds()
+            .map_or(0, |holds| holds.end() - holds.start() + 1)
+    }
+}
+
+/**
+ * A race too long for [`Race`], solved the same way with big integers.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigRace {
+    pub time: BigUint,
+    pub record: BigUint,
+}
+
+impl BigRace {
+    fn wins(&self, hold: &BigUint) -> bool {
+        hold * (&self.time - hold) > self.record
+    }
+
+    /**
+     * Every hold time that beats the record, or None if none does.
+     */
+    pub fn winning_holds(&self) -> Option<RangeInclusive<BigUint>> {
+        let square = &self.time * &self.time;
+        let four_record = &self.record * 4u32;
+        if square < four_record {
+            return None;
+        }
+        let root = (square - four_record).sqrt();
+        let mut low: BigUint = (&self.time - root) / 2u32;
+        let middle = &self.time / 2u32;
+        while low <= middle && !self.wins(&low) {
+            low += 1u32;
+        }
+        while low > BigUint::ZERO && self.wins(&(&low - 1u32)) {
+            low -= 1u32;
+        }
+        if low > middle || !self.wins(&low) {
+            return None;
+        }
+        let high = &self.time - &low;
+        Some(low..=high)
+    }
+
+    pub fn ways_to_win(&self) -> BigUint {
+        self.winning_holds()
+            .map_or(BigUint::ZERO, |holds| holds.end() - holds.start() + 1u32)
+    }
+}
+
+impl From<Race> for BigRace {
+    fn from(race: Race) -> Self {
+        BigRace {
+            time: race.time.into(),
+            record: race.record.into(),
+        }
+    }
+}
+
+fn part1_parse_line(line: &str) -> Result<Vec<u64>, ParseError> {
+    line.split_whitespace()
+        .skip(1)
+        .map(|num_str| parse_number(line, num_str))
+        .collect()
+}
+
+fn part1_parse(input: &str) -> Result<Vec<Race>, ParseError> {
+    let mut results = Vec::new();
+    let numbers: Vec<Vec<u64>> = parse_lines(input, part1_parse_line)?;
+    let [times, records] = numbers.as_slice() else {
+        return Err(ParseError::new(
+            input,
+            input,
+            "expected a time and a distance line",
+        ));
+    };
+    if times.len() != records.len() {
+        return Err(ParseError::new(
+            input,
+            input,
+            "expected a distance for every time",
+        ));
+    }
+    for i in 0..times.len() {
+        results.push(Race {
+            time: times[i],
+            record: records[i],
+        });
+    }
+    Ok(results)
+}
+
+fn part2_parse_line<T: std::str::FromStr>(line: &str) -> Result<T, ParseError> {
+    let (_, value) = split_once(line, line, ":")?;
+    let str_value: String = value.chars().filter(|&c| !c.is_whitespace()).collect();
+    str_value
+        .parse()
+        .map_err(|_| ParseError::new(line, value.trim(), "invalid number"))
+}
+
+fn part2_lines<T: std::str::FromStr>(input: &str) -> Result<[T; 2], ParseError> {
+    parse_lines(input, part2_parse_line)?
+        .try_into()
+        .map_err(|_| ParseError::new(input, input, "expected a time and a distance line"))
+}
+
+fn part2_parse(input: &str) -> Result<Race, ParseError> {
+    let [time, record] = part2_lines(input)?;
+    Ok(Race { time, record })
+}
+
+/**
+ * The sheet read as one long race, however many digits it has.
+ */
+pub fn parse_big_race(input: &str) -> Result<BigRace, ParseError> {
+    let [time, record] = part2_lines(input)?;
+    Ok(BigRace { time, record })
+}
+
+/**
+ * The race sheet read both ways: as separate races for part 1 and as one
+ * long race (ignoring the spaces between numbers) for part 2.
+ */
+pub struct Sheet {
+    races: Vec<Race>,
+    race: Race,
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+
+    type Input<'a> = Sheet;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Sheet, ParseError> {
+        Ok(Sheet {
+            races: part1_parse(input)?,
+            race: part2_parse(input)?,
+        })
+    }
+
+    fn part1(sheet: &Sheet) -> u64 {
+        sheet.races.iter().map(Race::ways_to_win).product()
+    }
+
+    fn part2(sheet: &Sheet) -> u64 {
+        sheet.race.ways_to_win()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day06::part1(&Day06::parse(SAMPLE).unwrap()), 288)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day06::part2(&Day06::parse(SAMPLE).unwrap()), 71503)
+    }
+
+    fn brute_force(race: &Race) -> Option<RangeInclusive<u64>> {
+        let wins: Vec<u64> = (0..=race.time)
+            .filter(|&hold| hold * (race.time - hold) > race.record)
+            .collect();
+        Some(*wins.first()?..=*wins.last()?)
+    }
+
+    #[test]
+    fn test_winning_holds_brute_force() {
+        for time in 0..80 {
+            for record in 0..time * time / 4 + 3 {
+                let race = Race { time, record };
+                let expected = brute_force(&race);
+                assert_eq!(race.winning_holds(), expected, "{race:?}");
+                let big = BigRace::from(race).winning_holds();
+                let expected =
+                    expected.map(|r| BigUint::from(*r.start())..=BigUint::from(*r.end()));
+                assert_eq!(big, expected, "{race:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_winning_holds_exact_roots() {
+        // Roots 10 and 20 are exact, and neither wins.
+        let race = Race {
+            time: 30,
+            record: 200,
+        };
+        assert_eq!(race.winning_holds(), Some(11..=19));
+        // Roots 3 and 7: only 4, 5 and 6 win.
+        let race = Race {
+            time: 10,
+            record: 21,
+        };
+        assert_eq!(race.ways_to_win(), 3);
+        // 5 * 5 ties the record.
+        let race = Race {
+            time: 10,
+            record: 25,
+        };
+        assert_eq!(race.winning_holds(), None);
+    }
+
+    #[test]
+    fn test_winning_holds_large() {
+        // Roots 2^31 and 2^32 + 1, far past f64 precision once multiplied.
+        let (low, high) = (1u64 << 31, (1u64 << 32) + 1);
+        let race = Race {
+            time: low + high,
+            record: low * high,
+        };
+        assert_eq!(race.winning_holds(), Some(low + 1..=high - 1));
+        let race = Race {
+            time: u64::MAX,
+            record: u64::MAX,
+        };
+        assert_eq!(race.winning_holds(), Some(2..=u64::MAX - 2));
+        let big = BigRace::from(race);
+        assert_eq!(big.ways_to_win(), BigUint::from(u64::MAX - 3));
+    }
+
+    #[test]
+    fn test_big_race() {
+        let race = parse_big_race(SAMPLE).unwrap();
+        assert_eq!(race.ways_to_win(), BigUint::from(71503u32));
+        // Roots 10^20 and 10^20 + 10, both beyond u64.
+        let sheet =
+            "Time: 200000000000000000010\nDistance: 10000000000000000001000000000000000000000";
+        let race = parse_big_race(sheet).unwrap();
+        assert_eq!(race.ways_to_win(), BigUint::from(9u32));
+        assert!(Day06::parse(sheet).is_err());
+    }
+}
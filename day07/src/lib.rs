@@ -0,0 +1,401 @@
+pub mod rules;
+
+use common::parse_chars;
+use common::parse_lines;
+use common::parse_number;
+use common::ParseError;
+use common::Solution;
+use rules::CardRules;
+use rules::Jokers;
+use rules::Standard;
+use std::fmt::Write;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct Hand {
+    hand_strength: usize,
+    cards: Vec<u8>,
+    bid: usize,
+}
+
+impl Hand {
+    fn new(rules: &dyn CardRules, str_hand: &str, bid: usize) -> Hand {
+        Hand {
+            hand_strength: rules.hand_type(str_hand),
+            cards: rules.tie_break(str_hand),
+            bid,
+        }
+    }
+}
+
+fn parse_line<'a>(line: &'a str, rules: &dyn CardRules) -> Result<(&'a str, usize), ParseError> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let [str_hand, str_bid] = parts[..] else {
+        return Err(ParseError::new(line, line, "expected a hand and a bid"));
+    };
+    parse_chars(str_hand, |c| rules.card_value(c), "invalid card")
+        .map_err(|err| err.within(line, str_hand))?;
+    let bid: usize = parse_number(line, str_bid)?;
+    Ok((str_hand, bid))
+}
+
+/**
+ * The hands and bids of `input`, checking every card is in the deck of
+ * `rules` and every hand has as many cards as the first.
+ */
+pub fn parse_hands<'a>(
+    input: &'a str,
+    rules: &dyn CardRules,
+) -> Result<Vec<(&'a str, usize)>, ParseError> {
+    let hands = parse_lines(input, |line| parse_line(line, rules))?;
+    if let Some((first, _)) = hands.first() {
+        let size = first.chars().count();
+        if let Some((hand, _)) = hands.iter().find(|(hand, _)| hand.chars().count() != size) {
+            let message = format!("expected {size} cards");
+            return Err(ParseError::new(input, hand, message));
+        }
+    }
+    Ok(hands)
+}
+
+/**
+ * Total winnings of `hands` ranked by `rules`, whose deck they must use.
+ */
+pub fn winnings(hands: &[(&str, usize)], rules: &dyn CardRules) -> usize {
+    explain(hands, rules).iter().map(|row| row.winnings).sum()
+}
+
+/**
+ * How one hand placed in the tournament.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation<'a> {
+    /** 1-based, in the order the hands were given. */
+    pub line: usize,
+    pub hand: &'a str,
+    /** The hand with its wild cards replaced by what they stood in for. */
+    pub played_as: String,
+    /** Index of the hand's type in the rules' catalogue. */
+    pub strength: usize,
+    pub hand_type: String,
+    pub bid: usize,
+    /** 1 for the weakest hand. */
+    pub rank: usize,
+    pub winnings: usize,
+}
+
+/**
+ * Every hand's type, rank and winnings under `rules`, in input order.
+ */
+pub fn explain<'a>(hands: &[(&'a str, usize)], rules: &dyn CardRules) -> Vec<Explanation<'a>> {
+    let ranked: Vec<Hand> = hands
+        .iter()
+        .map(|&(str_hand, bid)| Hand::new(rules, str_hand, bid))
+        .collect();
+    let mut order: Vec<usize> = (0..ranked.len()).collect();
+    order.sort_by(|&a, &b| ranked[a].cmp(&ranked[b]));
+    let mut ranks = vec![0; ranked.len()];
+    for (index, &hand) in order.iter().enumerate() {
+        ranks[hand] = index + 1;
+    }
+    let types = rules.hand_types().types();
+    hands
+        .iter()
+        .zip(&ranked)
+        .zip(ranks)
+        .enumerate()
+        .map(|(index, ((&(str_hand, bid), hand), rank))| Explanation {
+            line: index + 1,
+            hand: str_hand,
+            played_as: rules.substitute(str_hand),
+            strength: hand.hand_strength,
+            hand_type: types[hand.hand_strength].name.clone(),
+            bid,
+            rank,
+            winnings: rank * bid,
+        })
+        .collect()
+}
+
+/**
+ * What to order explanations by. Ties keep their rank order.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Line,
+    Hand,
+    Type,
+    Bid,
+    Rank,
+    Winnings,
+}
+
+impl SortBy {
+    pub fn parse(name: &str) -> Option<SortBy> {
+        match name {
+            "line" => Some(SortBy::Line),
+            "hand" => Some(SortBy::Hand),
+            "type" => Some(SortBy::Type),
+            "bid" => Some(SortBy::Bid),
+            "rank" => Some(SortBy::Rank),
+            "winnings" => Some(SortBy::Winnings),
+            _ => None,
+        }
+    }
+}
+
+pub fn sort_explanations(explanations: &mut [Explanation], by: SortBy) {
+    explanations.sort_by_key(|row| row.rank);
+    match by {
+        SortBy::Line => explanations.sort_by_key(|row| row.line),
+        SortBy::Hand => explanations.sort_by_key(|row| row.hand),
+        SortBy::Type => explanations.sort_by_key(|row| row.strength),
+        SortBy::Bid => explanations.sort_by_key(|row| row.bid),
+        SortBy::Rank => {}
+        SortBy::Winnings => explanations.sort_by_key(|row| row.winnings),
+    }
+}
+
+/**
+ * One aligned row per hand, then the total winnings.
+ */
+pub fn render_table(explanations: &[Explanation]) -> String {
+    let hand_width = explanations
+        .iter()
+        .map(|row| row.hand.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let type_width = explanations
+        .iter()
+        .map(|row| row.hand_type.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut output = format!(
+        "{:>5}  {:hand_width$}  {:hand_width$}  {:type_width$} {:>7} {:>6} {:>9}\n",
+        "line", "hand", "as", "type", "bid", "rank", "winnings"
+    );
+    for row in explanations {
+        writeln!(
+            output,
+            "{:>5}  {:hand_width$}  {:hand_width$}  {:type_width$} {:>7} {:>6} {:>9}",
+            row.line, row.hand, row.played_as, row.hand_type, row.bid, row.rank, row.winnings
+        )
+        .expect("writing to a String");
+    }
+    let total: usize = explanations.iter().map(|row| row.winnings).sum();
+    writeln!(output, "total winnings: {total}").expect("writing to a String");
+    output
+}
+
+/**
+ * Quote `field` for CSV if it needs it.
+ */
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/**
+ * The explanations as CSV with a header row.
+ */
+pub fn render_csv(explanations: &[Explanation]) -> String {
+    let mut output = String::from("line,hand,played_as,type,bid,rank,winnings\n");
+    for row in explanations {
+        writeln!(
+            output,
+            "{},{},{},{},{},{},{}",
+            row.line,
+            csv_field(row.hand),
+            csv_field(&row.played_as),
+            csv_field(&row.hand_type),
+            row.bid,
+            row.rank,
+            row.winnings
+        )
+        .expect("writing to a String");
+    }
+    output
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+
+    type Input<'a> = Vec<(&'a str, usize)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<(&str, usize)>, ParseError> {
+        // Jokers only change the J's meaning, so both parts share a deck.
+        parse_hands(input, &Standard)
+    }
+
+    fn part1(hands: &Vec<(&str, usize)>) -> usize {
+        winnings(hands, &Standard)
+    }
+
+    fn part2(hands: &Vec<(&str, usize)>) -> usize {
+        winnings(hands, &Jokers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rules::*;
+
+    // Indices into the standard hand types.
+    const ONE_PAIR: usize = 1;
+    const TWO_PAIR: usize = 2;
+    const THREE_OF_A_KIND: usize = 3;
+    const FOUR_OF_A_KIND: usize = 5;
+
+    static SAMPLE: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day07::part1(&Day07::parse(SAMPLE).unwrap()), 6440)
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(parse_line("32T3K 765", &Standard), Ok(("32T3K", 765)));
+        assert_eq!(parse_line("32X3K 765", &Standard).unwrap_err().column, 3);
+        assert_eq!(
+            parse_line("32T3K", &Standard).unwrap_err().message,
+            "expected a hand and a bid"
+        );
+    }
+
+    #[test]
+    fn test_parse_hands() {
+        let err = parse_hands("32T3K 765\n32T3 1", &Standard).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "expected 5 cards");
+        let hands = parse_hands("KAK 1\n222 2\n234 3", &Standard).unwrap();
+        assert_eq!(winnings(&hands, &Standard), 3 + 2 + 3 * 2);
+    }
+
+    #[test]
+    fn test_explain() {
+        let hands = Day07::parse(SAMPLE).unwrap();
+        let mut explanations = explain(&hands, &Jokers);
+        assert_eq!(
+            explanations[3],
+            Explanation {
+                line: 4,
+                hand: "KTJJT",
+                played_as: "KTTTT".to_string(),
+                strength: FOUR_OF_A_KIND,
+                hand_type: "Four of a kind".to_string(),
+                bid: 220,
+                rank: 5,
+                winnings: 1100,
+            }
+        );
+        sort_explanations(&mut explanations, SortBy::Rank);
+        let hands: Vec<&str> = explanations.iter().map(|row| row.hand).collect();
+        assert_eq!(hands, ["32T3K", "KK677", "T55J5", "QQQJA", "KTJJT"]);
+        sort_explanations(&mut explanations, SortBy::Winnings);
+        assert_eq!(explanations[0].winnings, 56);
+        assert_eq!(explanations[4].winnings, 2052);
+    }
+
+    #[test]
+    fn test_render() {
+        let hands = Day07::parse(SAMPLE).unwrap();
+        let explanations = explain(&hands[..2], &Jokers);
+        assert_eq!(
+            render_table(&explanations),
+            " line  hand   as     type               bid   rank  winnings
+    1  32T3K  32T3K  One pair           765      1       765
+    2  T55J5  T5555  Four of a kind     684      2      1368
+total winnings: 2133
+"
+        );
+        assert_eq!(
+            render_csv(&explanations),
+            "line,hand,played_as,type,bid,rank,winnings
+1,32T3K,32T3K,One pair,765,1,765
+2,T55J5,T5555,Four of a kind,684,2,1368
+"
+        );
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn test_parse_part2() {
+        assert_eq!(
+            Hand::new(&Jokers, "32T3K", 765),
+            Hand {
+                hand_strength: ONE_PAIR,
+                cards: vec!(3, 2, 10, 3, 13),
+                bid: 765,
+            }
+        );
+        assert_eq!(
+            Hand::new(&Jokers, "T55J5", 684),
+            Hand {
+                hand_strength: FOUR_OF_A_KIND,
+                cards: vec!(10, 5, 5, 1, 5),
+                bid: 684,
+            }
+        );
+        assert_eq!(
+            Hand::new(&Jokers, "KK677", 28),
+            Hand {
+                hand_strength: TWO_PAIR,
+                cards: vec!(13, 13, 6, 7, 7),
+                bid: 28,
+            }
+        );
+        assert_eq!(
+            Hand::new(&Jokers, "KTJJT", 220),
+            Hand {
+                hand_strength: FOUR_OF_A_KIND,
+                cards: vec!(13, 10, 1, 1, 10),
+                bid: 220,
+            }
+        );
+        assert_eq!(
+            Hand::new(&Jokers, "QQQJA", 483),
+            Hand {
+                hand_strength: FOUR_OF_A_KIND,
+                cards: vec!(12, 12, 12, 1, 14),
+                bid: 483,
+            }
+        );
+        assert_eq!(
+            Hand::new(&Jokers, "4JJKQ", 84),
+            Hand {
+                hand_strength: THREE_OF_A_KIND,
+                cards: vec!(4, 1, 1, 13, 12),
+                bid: 84,
+            }
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day07::part2(&Day07::parse(SAMPLE).unwrap()), 5905)
+    }
+
+    #[test]
+    fn test_rule_set_winnings() {
+        let rules = RuleSet::parse("ranks = J23456789TQKA\nwild = J").unwrap();
+        let hands = parse_hands(SAMPLE, &rules).unwrap();
+        assert_eq!(winnings(&hands, &rules), 5905);
+        let rules = RuleSet::parse("ranks = 23456789TJQK").unwrap();
+        let err = parse_hands(SAMPLE, &rules).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 5));
+    }
+}
@@ -1,267 +1,113 @@
+use common::Solution;
+use day07::rules::CardRules;
+use day07::rules::Jokers;
+use day07::rules::RuleSet;
+use day07::rules::Standard;
+use day07::Day07;
+use day07::SortBy;
+use std::env;
 use std::fs;
-use std::io;
+use std::process::ExitCode;
 
-fn main() -> Result<(), io::Error> {
-    let file_path = "input.txt";
-    let file_contents = fs::read_to_string(file_path)?;
-    let input = file_contents.as_str();
+const EXPLAIN_USAGE: &str = "Usage: --explain <1|2|--rules <config>> \
+[--sort <line|hand|type|bid|rank|winnings>] [--csv] [--input <file|->] [--input-dir <dir>]";
 
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+const RULES_USAGE: &str = "Usage: --rules <config> [--input <file|->] [--input-dir <dir>]";
 
-    Ok(())
+/**
+ * The rule set in the config file at `path`.
+ */
+fn read_rules(path: &str) -> Result<RuleSet, String> {
+    let config = fs::read_to_string(path).map_err(|err| format!("failed to read {path}: {err}"))?;
+    RuleSet::parse(&config).map_err(|err| format!("{path}: {err}"))
 }
 
-fn part1_card_value(c: char) -> u8 {
-    if let Some(x) = c.to_digit(10) {
-        x as u8
-    } else {
-        match c {
-            'T' => 10,
-            'J' => 11,
-            'Q' => 12,
-            'K' => 13,
-            'A' => 14,
-            _ => panic!("Invalid input!"),
-        }
-    }
+/**
+ * `--rules <config> [--input <file|->] [--input-dir <dir>]`: total winnings
+ * under the rule set in a config file.
+ */
+fn run_rules(args: &[String]) -> Result<String, String> {
+    let (path, args) = args.split_first().ok_or(RULES_USAGE)?;
+    let rules = read_rules(path)?;
+    let source = common::input_from_args(args)?;
+    let input = source.read(Day07::DAY).map_err(|err| err.to_string())?;
+    let hands = day07::parse_hands(&input, &rules)
+        .map_err(|err| format!("{}: {err}", source.name(Day07::DAY)))?;
+    Ok(format!("Winnings: {}\n", day07::winnings(&hands, &rules)))
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
-struct Hand {
-    hand_strength: u8,
-    cards: Vec<u8>,
-    bid: usize,
-}
-
-const HIGH_CARD: u8 = 0;
-const ONE_PAIR: u8 = 1;
-const TWO_PAIR: u8 = 2;
-const THREE_OF_A_KIND: u8 = 3;
-const FULLHOUSE: u8 = 4;
-const FOUR_OF_A_KIND: u8 = 5;
-const FIVE_OF_A_KIND: u8 = 6;
-
-struct CardCount {
-    card: u8,
-    count: u8,
-}
-
-fn part1_parse_hand(str_hand: &str, bid: usize) -> Hand {
-    let cards: Vec<u8> = str_hand.chars().map(|c| part1_card_value(c)).collect();
-    let mut card_counts: Vec<CardCount> = Vec::new();
-    for card in &cards {
-        let mut found = false;
-        for cc in card_counts.iter_mut() {
-            if cc.card == *card {
-                cc.count += 1;
-                found = true;
+/**
+ * `--explain <1|2|--rules <config>> [--sort <column>] [--csv]
+ * [--input <file|->] [--input-dir <dir>]`: each hand's type, rank and
+ * winnings under the rules of either part or of a config file.
+ */
+fn run_explain(args: &[String]) -> Result<String, String> {
+    let (rules, mut args): (Box<dyn CardRules>, &[String]) = match args {
+        [flag, path, rest @ ..] if flag == "--rules" => (Box::new(read_rules(path)?), rest),
+        [part, rest @ ..] => match part.as_str() {
+            "1" => (Box::new(Standard), rest),
+            "2" => (Box::new(Jokers), rest),
+            _ => {
+                return Err(format!(
+                    "invalid part '{part}', expected 1, 2 or --rules <config>"
+                ))
             }
-        }
-        if !found {
-            card_counts.push(CardCount {
-                card: *card,
-                count: 1,
-            });
-        }
-    }
-    let hand_strength = if card_counts[0].count == 5 {
-        FIVE_OF_A_KIND
-    } else if card_counts[0].count == 4 || card_counts[1].count == 4 {
-        FOUR_OF_A_KIND
-    } else if card_counts.iter().filter(|cc| cc.count == 3).count() == 1 {
-        if card_counts.len() == 2 {
-            FULLHOUSE
-        } else {
-            THREE_OF_A_KIND
-        }
-    } else {
-        let pairs = card_counts.iter().filter(|cc| cc.count == 2).count();
-        if pairs == 2 {
-            TWO_PAIR
-        } else if pairs == 1 {
-            ONE_PAIR
-        } else {
-            HIGH_CARD
-        }
+        },
+        [] => return Err(EXPLAIN_USAGE.to_string()),
     };
-    Hand {
-        hand_strength,
-        cards,
-        bid,
-    }
-}
-
-fn parse_line(line: &str, parse_hand: fn(&str, usize) -> Hand) -> Hand {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    let bid: usize = parts[1].parse().unwrap();
-    parse_hand(parts[0], bid)
-}
-
-fn parse(input: &str, parse_hand: fn(&str, usize) -> Hand) -> Vec<Hand> {
-    input
-        .lines()
-        .map(|line| parse_line(line, parse_hand))
-        .collect()
-}
-
-fn calculate_winnings(input: &str, parse_hand: fn(&str, usize) -> Hand) -> usize {
-    let mut hands = parse(input, parse_hand);
-    hands.sort();
-    let mut total = 0;
-    for (index, hand) in hands.iter().enumerate() {
-        total += (index + 1) * hand.bid;
-    }
-    total
-}
-
-fn part1(input: &str) -> usize {
-    calculate_winnings(input, part1_parse_hand)
-}
-
-fn part2_card_value(c: char) -> u8 {
-    if c == 'J' {
-        1
-    } else {
-        part1_card_value(c)
-    }
-}
-
-fn part2_parse_hand(str_hand: &str, bid: usize) -> Hand {
-    let cards: Vec<u8> = str_hand.chars().map(|c| part2_card_value(c)).collect();
-    let mut card_counts: Vec<CardCount> = Vec::new();
-    let mut jokers = 0;
-    for card in &cards {
-        if *card == 1 {
-            jokers += 1;
-        } else {
-            let mut found = false;
-            for cc in card_counts.iter_mut() {
-                if cc.card == *card {
-                    cc.count += 1;
-                    found = true;
-                }
+    let rules = rules.as_ref();
+    let mut sort_by = SortBy::Line;
+    let mut csv = false;
+    loop {
+        match args {
+            [flag, column, rest @ ..] if flag == "--sort" => {
+                sort_by = SortBy::parse(column)
+                    .ok_or_else(|| format!("invalid column '{column}'\n{EXPLAIN_USAGE}"))?;
+                args = rest;
             }
-            if !found {
-                card_counts.push(CardCount {
-                    card: *card,
-                    count: 1,
-                });
+            [flag, rest @ ..] if flag == "--csv" => {
+                csv = true;
+                args = rest;
             }
+            _ => break,
         }
     }
-    let has_four_kind = card_counts.iter().filter(|cc| cc.count == 4).count() == 1;
-    let has_three_kind = card_counts.iter().filter(|cc| cc.count == 3).count() == 1;
-    let pairs = card_counts.iter().filter(|cc| cc.count == 2).count();
-    let hand_strength = if (card_counts.len() == 1 && card_counts[0].count == 5)
-        || (has_four_kind && jokers == 1)
-        || (has_three_kind && jokers == 2)
-        || (pairs == 1 && jokers == 3)
-        || jokers >= 4
-    {
-        FIVE_OF_A_KIND
-    } else if has_four_kind
-        || (has_three_kind && jokers == 1)
-        || (pairs == 1 && jokers == 2)
-        || jokers == 3
-    {
-        FOUR_OF_A_KIND
-    } else if (has_three_kind && pairs == 1)
-        || (has_three_kind && jokers == 1)
-        || (pairs == 2 && jokers == 1)
-        || (pairs == 1 && jokers == 2)
-    {
-        FULLHOUSE
-    } else if has_three_kind || (pairs == 1 && jokers == 1) || jokers == 2 {
-        THREE_OF_A_KIND
-    } else if pairs == 2 || (pairs == 1 && jokers == 1) {
-        TWO_PAIR
-    } else if pairs == 1 || jokers == 1 {
-        ONE_PAIR
+    let source = common::input_from_args(args)?;
+    let input = source.read(Day07::DAY).map_err(|err| err.to_string())?;
+    let hands = day07::parse_hands(&input, rules)
+        .map_err(|err| format!("{}: {err}", source.name(Day07::DAY)))?;
+    let mut explanations = day07::explain(&hands, rules);
+    day07::sort_explanations(&mut explanations, sort_by);
+    if csv {
+        Ok(day07::render_csv(&explanations))
     } else {
-        HIGH_CARD
-    };
-    let results = Hand {
-        hand_strength,
-        cards,
-        bid,
-    };
-    results
-}
-
-fn part2(input: &str) -> usize {
-    calculate_winnings(input, part2_parse_hand)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    static SAMPLE: &str = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(SAMPLE), 6440)
+        Ok(day07::render_table(&explanations))
     }
+}
 
-    #[test]
-    fn test_parse_part2() {
-        assert_eq!(
-            part2_parse_hand("32T3K", 765),
-            Hand {
-                hand_strength: ONE_PAIR,
-                cards: vec!(3, 2, 10, 3, 13),
-                bid: 765,
-            }
-        );
-        assert_eq!(
-            part2_parse_hand("T55J5", 684),
-            Hand {
-                hand_strength: FOUR_OF_A_KIND,
-                cards: vec!(10, 5, 5, 1, 5),
-                bid: 684,
-            }
-        );
-        assert_eq!(
-            part2_parse_hand("KK677", 28),
-            Hand {
-                hand_strength: TWO_PAIR,
-                cards: vec!(13, 13, 6, 7, 7),
-                bid: 28,
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.split_first() {
+        Some((flag, rest)) if flag == "--explain" => match run_explain(rest) {
+            Ok(output) => {
+                print!("{output}");
+                ExitCode::SUCCESS
             }
-        );
-        assert_eq!(
-            part2_parse_hand("KTJJT", 220),
-            Hand {
-                hand_strength: FOUR_OF_A_KIND,
-                cards: vec!(13, 10, 1, 1, 10),
-                bid: 220,
+            Err(err) => {
+                eprintln!("{err}");
+                ExitCode::FAILURE
             }
-        );
-        assert_eq!(
-            part2_parse_hand("QQQJA", 483),
-            Hand {
-                hand_strength: FOUR_OF_A_KIND,
-                cards: vec!(12, 12, 12, 1, 14),
-                bid: 483,
+        },
+        Some((flag, rest)) if flag == "--rules" => match run_rules(rest) {
+            Ok(output) => {
+                print!("{output}");
+                ExitCode::SUCCESS
             }
-        );
-        assert_eq!(
-            part2_parse_hand("4JJKQ", 84),
-            Hand {
-                hand_strength: THREE_OF_A_KIND,
-                cards: vec!(4, 1, 1, 13, 12),
-                bid: 84,
+            Err(err) => {
+                eprintln!("{err}");
+                ExitCode::FAILURE
             }
-        );
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(SAMPLE), 5905)
+        },
+        _ => common::main::<Day07>(),
     }
 }
@@ -0,0 +1,461 @@
+use common::parse_lines;
+use common::parse_number;
+use common::split_once;
+use common::ParseError;
+use std::cmp::Reverse;
+use std::iter;
+use std::sync::LazyLock;
+
+/**
+ * A kind of hand, such as a full house: `groups` are the sizes of the sets
+ * of equal cards it needs, largest first, so `[3, 2]`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandType {
+    pub name: String,
+    pub groups: Vec<usize>,
+}
+
+impl HandType {
+    pub fn new(name: &str, groups: &[usize]) -> HandType {
+        let mut groups = groups.to_vec();
+        groups.sort_by(|a, b| b.cmp(a));
+        HandType {
+            name: name.to_string(),
+            groups,
+        }
+    }
+
+    /**
+     * Whether a hand whose equal cards come in groups of `counts`, largest
+     * first, and with `wild` wild cards can be this type.
+     */
+    fn fits(&self, counts: &[usize], wild: usize) -> bool {
+        // Growing the largest groups to the largest needs is never worse
+        // than any other pairing.
+        let missing: usize = self
+            .groups
+            .iter()
+            .enumerate()
+            .map(|(i, need)| need.saturating_sub(counts.get(i).copied().unwrap_or(0)))
+            .sum();
+        missing <= wild
+    }
+}
+
+/**
+ * Hand types from weakest to strongest. A hand is the strongest type it can
+ * make, whatever its length.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Catalogue {
+    types: Vec<HandType>,
+}
+
+static STANDARD: LazyLock<Catalogue> = LazyLock::new(|| Catalogue {
+    types: vec![
+        HandType::new("High card", &[]),
+        HandType::new("One pair", &[2]),
+        HandType::new("Two pair", &[2, 2]),
+        HandType::new("Three of a kind", &[3]),
+        HandType::new("Full house", &[3, 2]),
+        HandType::new("Four of a kind", &[4]),
+        HandType::new("Five of a kind", &[5]),
+    ],
+});
+
+impl Catalogue {
+    /**
+     * The catalogue of `types`, weakest first, or None if none of them can
+     * be made by every hand.
+     */
+    pub fn new(types: Vec<HandType>) -> Option<Catalogue> {
+        types
+            .iter()
+            .any(|hand_type| hand_type.groups.is_empty())
+            .then_some(Catalogue { types })
+    }
+
+    pub fn standard() -> &'static Catalogue {
+        &STANDARD
+    }
+
+    pub fn types(&self) -> &[HandType] {
+        &self.types
+    }
+
+    /**
+     * Index in `types` of the strongest type `hand` can make, with wild
+     * cards standing in for whichever cards help most.
+     */
+    pub fn classify(&self, hand: &str, is_wild: impl Fn(char) -> bool) -> usize {
+        let (groups, wild) = card_groups(hand, is_wild);
+        let mut counts: Vec<usize> = groups.into_iter().map(|(_, count)| count).collect();
+        counts.sort_by(|a, b| b.cmp(a));
+        self.types
+            .iter()
+            .rposition(|hand_type| hand_type.fits(&counts, wild))
+            .expect("a catalogue has a type every hand fits")
+    }
+}
+
+/**
+ * The natural cards of `hand` as (card, count) in order of first
+ * appearance, and the number of wild cards.
+ */
+fn card_groups(hand: &str, is_wild: impl Fn(char) -> bool) -> (Vec<(char, usize)>, usize) {
+    let mut groups: Vec<(char, usize)> = Vec::new();
+    let mut wild = 0;
+    for card in hand.chars() {
+        if is_wild(card) {
+            wild += 1;
+        } else if let Some((_, count)) = groups.iter_mut().find(|(c, _)| *c == card) {
+            *count += 1;
+        } else {
+            groups.push((card, 1));
+        }
+    }
+    (groups, wild)
+}
+
+/**
+ * How a game of Camel Cards ranks cards and hands. Only `card_value` is
+ * required; the rest default to the shared classifier.
+ */
+pub trait CardRules {
+    /**
+     * The card's rank, higher beating lower, or None if it is not in the
+     * deck.
+     */
+    fn card_value(&self, card: char) -> Option<u8>;
+
+    /**
+     * Whether the card stands in for whichever card makes the best hand.
+     */
+    fn is_wild(&self, _card: char) -> bool {
+        false
+    }
+
+    fn hand_types(&self) -> &Catalogue {
+        Catalogue::standard()
+    }
+
+    /**
+     * Index in `hand_types` of the hand's type.
+     */
+    fn hand_type(&self, hand: &str) -> usize {
+        self.hand_types().classify(hand, |card| self.is_wild(card))
+    }
+
+    /**
+     * `hand` with each wild card replaced by the card it stands in for to
+     * make its type, preferring stronger cards. Wild cards that would start
+     * a new group of their own are left as they are.
+     */
+    fn substitute(&self, hand: &str) -> String {
+        let (mut groups, _) = card_groups(hand, |card| self.is_wild(card));
+        groups.sort_by_key(|&(card, count)| Reverse((count, self.card_value(card))));
+        let needs = &self.hand_types().types()[self.hand_type(hand)].groups;
+        let mut fills = groups
+            .iter()
+            .zip(needs)
+            .flat_map(|(&(card, count), &need)| iter::repeat_n(card, need.saturating_sub(count)));
+        let mut keep: usize = needs.iter().skip(groups.len()).sum();
+        hand.chars()
+            .map(|card| {
+                if !self.is_wild(card) {
+                    return card;
+                }
+                if let Some(fill) = fills.next() {
+                    return fill;
+                }
+                if keep > 0 {
+                    keep -= 1;
+                    return card;
+                }
+                // Spare wild cards make the largest group larger still.
+                groups.first().map_or(card, |&(largest, _)| largest)
+            })
+            .collect()
+    }
+
+    /**
+     * Compared between hands of the same type: by default the card values
+     * in the order they were dealt.
+     */
+    fn tie_break(&self, hand: &str) -> Vec<u8> {
+        hand.chars()
+            .map(|card| self.card_value(card).expect("cards are validated by parse"))
+            .collect()
+    }
+}
+
+/**
+ * Part 1: `2` to `9`, then `T`, `J`, `Q`, `K` and `A`.
+ */
+pub struct Standard;
+
+impl CardRules for Standard {
+    fn card_value(&self, card: char) -> Option<u8> {
+        match card {
+            '2'..='9' => card.to_digit(10).map(|x| x as u8),
+            'T' => Some(10),
+            'J' => Some(11),
+            'Q' => Some(12),
+            'K' => Some(13),
+            'A' => Some(14),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * Part 2: `J` is a joker, wild but the weakest card on a tie.
+ */
+pub struct Jokers;
+
+impl CardRules for Jokers {
+    fn card_value(&self, card: char) -> Option<u8> {
+        if card == 'J' {
+            Some(1)
+        } else {
+            Standard.card_value(card)
+        }
+    }
+
+    fn is_wild(&self, card: char) -> bool {
+        card == 'J'
+    }
+}
+
+/**
+ * Rules read from a config such as
+ *
+ * ```text
+ * # Jokers wild
+ * ranks = J23456789TQKA
+ * wild = J
+ * hand = Nothing:
+ * hand = Pair: 2
+ * hand = Triple: 3
+ * ```
+ *
+ * `ranks` lists the deck weakest first; `wild`, if given, lists any of
+ * them that are wild. Each `hand` line adds a hand type, weakest first,
+ * with the sizes of its groups of equal cards; without any, the standard
+ * types are used.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    ranks: Vec<char>,
+    wild: Vec<char>,
+    hand_types: Catalogue,
+}
+
+impl RuleSet {
+    pub fn parse(config: &str) -> Result<RuleSet, ParseError> {
+        let mut ranks: Option<Vec<char>> = None;
+        let mut wild = Vec::new();
+        let mut hand_types = Vec::new();
+        let settings = parse_lines(config, |line| {
+            let setting = line.trim();
+            if setting.is_empty() || setting.starts_with('#') {
+                return Ok(None);
+            }
+            let (key, value) = split_once(line, setting, "=")?;
+            Ok(Some((key.trim(), value.trim())))
+        })?;
+        for (key, value) in settings.into_iter().flatten() {
+            if key == "hand" {
+                hand_types.push(parse_hand_type(config, value)?);
+                continue;
+            }
+            let cards: Vec<char> = value.chars().filter(|c| !c.is_whitespace()).collect();
+            let target = match key {
+                "ranks" if ranks.is_none() => ranks.insert(Vec::new()),
+                "wild" if wild.is_empty() => &mut wild,
+                "ranks" | "wild" => return Err(ParseError::new(config, key, "repeated setting")),
+                _ => return Err(ParseError::new(config, key, "unknown setting")),
+            };
+            for card in cards {
+                if target.contains(&card) {
+                    return Err(ParseError::new(config, value, "repeated card"));
+                }
+                target.push(card);
+            }
+        }
+        let Some(ranks) = ranks else {
+            return Err(ParseError::new(config, config, "expected a ranks setting"));
+        };
+        if ranks.is_empty() || ranks.len() > usize::from(u8::MAX) {
+            return Err(ParseError::new(config, config, "expected 1 to 255 ranks"));
+        }
+        if let Some(card) = wild.iter().find(|card| !ranks.contains(card)) {
+            let message = format!("wild card '{card}' is not ranked");
+            return Err(ParseError::new(config, config, &message));
+        }
+        let hand_types = if hand_types.is_empty() {
+            Catalogue::standard().clone()
+        } else {
+            Catalogue::new(hand_types).ok_or_else(|| {
+                ParseError::new(config, config, "expected a hand type with no groups")
+            })?
+        };
+        Ok(RuleSet {
+            ranks,
+            wild,
+            hand_types,
+        })
+    }
+}
+
+/**
+ * `<name>: <group size>...`, a slice of `config`.
+ */
+fn parse_hand_type(config: &str, value: &str) -> Result<HandType, ParseError> {
+    let (name, groups) = split_once(config, value, ":")?;
+    let groups = groups
+        .split_whitespace()
+        .map(|group| match parse_number(config, group)? {
+            0 => Err(ParseError::new(config, group, "empty group")),
+            size => Ok(size),
+        })
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+    Ok(HandType::new(name.trim(), &groups))
+}
+
+impl CardRules for RuleSet {
+    fn card_value(&self, card: char) -> Option<u8> {
+        let index = self.ranks.iter().position(|&c| c == card)?;
+        Some(index as u8 + 1)
+    }
+
+    fn is_wild(&self, card: char) -> bool {
+        self.wild.contains(&card)
+    }
+
+    fn hand_types(&self) -> &Catalogue {
+        &self.hand_types
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_name<'a>(rules: &'a dyn CardRules, hand: &str) -> &'a str {
+        &rules.hand_types().types()[rules.hand_type(hand)].name
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(type_name(&Standard, "AAAAA"), "Five of a kind");
+        assert_eq!(type_name(&Standard, "AA8AA"), "Four of a kind");
+        assert_eq!(type_name(&Standard, "23332"), "Full house");
+        assert_eq!(type_name(&Standard, "TTT98"), "Three of a kind");
+        assert_eq!(type_name(&Standard, "23432"), "Two pair");
+        assert_eq!(type_name(&Standard, "A23A4"), "One pair");
+        assert_eq!(type_name(&Standard, "23456"), "High card");
+        assert_eq!(type_name(&Jokers, "JJJJJ"), "Five of a kind");
+        assert_eq!(type_name(&Jokers, "2233J"), "Full house");
+        assert_eq!(type_name(&Jokers, "4JJKQ"), "Three of a kind");
+    }
+
+    #[test]
+    fn test_classify_hand_sizes() {
+        assert_eq!(type_name(&Standard, "A"), "High card");
+        assert_eq!(type_name(&Standard, "KAK"), "One pair");
+        assert_eq!(type_name(&Standard, "222"), "Three of a kind");
+        assert_eq!(type_name(&Jokers, "J2"), "One pair");
+        assert_eq!(type_name(&Standard, "2233445"), "Two pair");
+        assert_eq!(type_name(&Standard, "2223334"), "Full house");
+        assert_eq!(type_name(&Standard, "2222333"), "Four of a kind");
+        assert_eq!(type_name(&Standard, "2222223"), "Five of a kind");
+        assert_eq!(type_name(&Jokers, "JJ23456"), "Three of a kind");
+        assert_eq!(type_name(&Jokers, "J223344"), "Full house");
+    }
+
+    #[test]
+    fn test_substitute() {
+        assert_eq!(Jokers.substitute("KTJJT"), "KTTTT");
+        assert_eq!(Jokers.substitute("QQQJA"), "QQQQA");
+        // Between equal groups the stronger card gains.
+        assert_eq!(Jokers.substitute("22JKK"), "22KKK");
+        assert_eq!(Jokers.substitute("J2345"), "52345");
+        assert_eq!(Jokers.substitute("JJJJJ"), "JJJJJ");
+        assert_eq!(Standard.substitute("KTJJT"), "KTJJT");
+        // Two pair needs a new group for the second wild card.
+        let types = vec![
+            HandType::new("Nothing", &[]),
+            HandType::new("Triple", &[3]),
+            HandType::new("Two pair", &[2, 2]),
+        ];
+        let rules = RuleSet {
+            ranks: "23W".chars().collect(),
+            wild: vec!['W'],
+            hand_types: Catalogue::new(types).unwrap(),
+        };
+        assert_eq!(rules.substitute("2WW2"), "2WW2");
+        assert_eq!(rules.substitute("2W3"), "233");
+    }
+
+    #[test]
+    fn test_catalogue() {
+        // A wild card makes two pair here, not the weaker three of a kind
+        // that joining the largest group would.
+        let types = vec![
+            HandType::new("Nothing", &[]),
+            HandType::new("Triple", &[3]),
+            HandType::new("Two pair", &[2, 2]),
+        ];
+        let catalogue = Catalogue::new(types).unwrap();
+        assert_eq!(catalogue.classify("22W3", |card| card == 'W'), 2);
+        assert_eq!(catalogue.classify("222", |_| false), 1);
+        assert_eq!(catalogue.classify("2233", |_| false), 2);
+        assert_eq!(Catalogue::new(vec![HandType::new("Pair", &[2])]), None);
+    }
+
+    #[test]
+    fn test_rule_set() {
+        let rules = RuleSet::parse("# jokers\nranks = J23456789TQKA\nwild = J\n").unwrap();
+        assert_eq!(rules.card_value('J'), Some(1));
+        assert_eq!(rules.card_value('A'), Some(13));
+        assert_eq!(rules.card_value('X'), None);
+        assert_eq!(type_name(&rules, "KTJJT"), "Four of a kind");
+        // Several wild cards pool together.
+        let rules = RuleSet::parse("ranks = W23456789TJQKA\nwild = WJ").unwrap();
+        assert_eq!(type_name(&rules, "W2J34"), "Three of a kind");
+        assert_eq!(type_name(&rules, "WJ2J2"), "Five of a kind");
+        let rules =
+            RuleSet::parse("ranks = 123\nhand = Nothing:\nhand = Pair: 2\nhand = Triple: 3")
+                .unwrap();
+        assert_eq!(type_name(&rules, "113"), "Pair");
+        assert_eq!(type_name(&rules, "1212"), "Pair");
+        assert_eq!(type_name(&rules, "2222"), "Triple");
+    }
+
+    #[test]
+    fn test_rule_set_errors() {
+        let message = |config| RuleSet::parse(config).unwrap_err().message;
+        assert_eq!(message("wild = J"), "expected a ranks setting");
+        assert_eq!(message("ranks = AKA"), "repeated card");
+        assert_eq!(message("ranks = AK\nranks = Q"), "repeated setting");
+        assert_eq!(message("ranks = AK\nsuits = 4"), "unknown setting");
+        assert_eq!(
+            message("ranks = AK\nwild = J"),
+            "wild card 'J' is not ranked"
+        );
+        assert_eq!(RuleSet::parse("ranks AK").unwrap_err().line, 1);
+        assert_eq!(
+            message("ranks = AK\nhand = Pair: 2"),
+            "expected a hand type with no groups"
+        );
+        assert_eq!(message("ranks = AK\nhand = Pair 2"), "expected \":\"");
+        assert_eq!(message("ranks = AK\nhand = None: 0"), "empty group");
+        let err = RuleSet::parse("ranks = AK\nhand = Pair: x").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (2, 14, "invalid number")
+        );
+    }
+}
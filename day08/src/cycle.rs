@@ -0,0 +1,227 @@
+use common::math;
+
+/**
+ * Where a walk through the network ends up. With a fixed list of
+ * directions the walk's state is its node and the index of the next
+ * direction, so it must eventually repeat a state and loop forever.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /** The state the loop begins at: a node and the next direction's index. */
+    pub entry: (u32, usize),
+    /** Steps taken before first reaching `entry`. */
+    pub start: usize,
+    pub length: usize,
+    /** Steps before `start` that end on a goal node. */
+    pub lead_in: Vec<usize>,
+    /** Offsets from `start`, below `length`, that end on a goal node. */
+    pub offsets: Vec<usize>,
+}
+
+impl Cycle {
+    /**
+     * Whether the walk is on a goal node after `step` steps.
+     */
+    pub fn hits(&self, step: usize) -> bool {
+        if step < self.start {
+            self.lead_in.contains(&step)
+        } else {
+            self.offsets
+                .binary_search(&((step - self.start) % self.length))
+                .is_ok()
+        }
+    }
+}
+
+/**
+ * Walk from `start` until a state repeats, over node ids below `nodes`.
+ * `step(node, direction)` is the node reached by taking direction index
+ * `direction` from `node`, out of `directions` in all. The step each state
+ * was first seen at is kept in a table indexed by state, allocated once.
+ */
+pub fn analyse(
+    start: u32,
+    nodes: usize,
+    directions: usize,
+    step: impl Fn(u32, usize) -> u32,
+    is_goal: impl Fn(u32) -> bool,
+) -> Cycle {
+    let mut seen: Vec<Option<usize>> = vec![None; nodes * directions];
+    let mut goals = Vec::new();
+    let mut node = start;
+    for steps in 0.. {
+        let direction = steps % directions;
+        let state = node as usize * directions + direction;
+        if let Some(first) = seen[state] {
+            let (lead_in, offsets) = goals.iter().partition(|&&goal| goal < first);
+            return Cycle {
+                entry: (node, direction),
+                start: first,
+                length: steps - first,
+                lead_in,
+                offsets: offsets.into_iter().map(|goal| goal - first).collect(),
+            };
+        }
+        seen[state] = Some(steps);
+        if is_goal(node) {
+            goals.push(steps);
+        }
+        node = step(node, direction);
+    }
+    unreachable!("the walk has finitely many states")
+}
+
+/**
+ * Most combinations of goal congruences `earliest_common` keeps before it
+ * falls back to trying goal steps one by one.
+ */
+const MAX_RESIDUES: usize = 1 << 20;
+
+/**
+ * The first step at which every walk is on a goal node, or None if there
+ * are no walks or they never all are at once.
+ *
+ * Steps before every walk is in its loop are checked one by one. After
+ * that each walk hits goals at `start + offset` modulo its length, so the
+ * answer is the smallest solution to one of the combinations of those
+ * congruences. Their number is the product of the walks' goal counts, so
+ * once it would pass `MAX_RESIDUES` only the first `MAX_RESIDUES` goal
+ * steps of the walk with the longest loop are tried instead, and a meeting
+ * after those is reported as None.
+ */
+pub fn earliest_common(cycles: &[Cycle]) -> Option<usize> {
+    if cycles.is_empty() {
+        return None;
+    }
+    let looping = cycles.iter().map(|cycle| cycle.start).max().unwrap_or(0);
+    if let Some(step) = (0..looping).find(|&step| cycles.iter().all(|cycle| cycle.hits(step))) {
+        return Some(step);
+    }
+    let mut residues: Vec<i128> = vec![0];
+    let mut modulus: i128 = 1;
+    for cycle in cycles {
+        if residues.len() * cycle.offsets.len() > MAX_RESIDUES {
+            return sieve(cycles, looping, MAX_RESIDUES);
+        }
+        let length = cycle.length as i128;
+        let mut combined: Vec<i128> = Vec::new();
+        for &residue in &residues {
+            for &offset in &cycle.offsets {
+                let goal = (cycle.start + offset) as i128;
+                if let Some((solution, _)) = math::crt([(residue, modulus), (goal, length)]) {
+                    combined.push(solution);
+                }
+            }
+        }
+        combined.sort();
+        combined.dedup();
+        residues = combined;
+        modulus = math::checked_lcm(modulus, length)?;
+    }
+    residues
+        .into_iter()
+        .map(|residue| math::next_congruent(residue, modulus, looping as i128) as usize)
+        .min()
+}
+
+/**
+ * The first of the next `limit` goal steps, from `looping` on, of the walk
+ * with the longest loop at which every walk is on a goal node.
+ */
+fn sieve(cycles: &[Cycle], looping: usize, limit: usize) -> Option<usize> {
+    let longest = cycles.iter().max_by_key(|cycle| cycle.length)?;
+    if longest.offsets.is_empty() {
+        return None;
+    }
+    (0..)
+        .flat_map(|lap| {
+            longest
+                .offsets
+                .iter()
+                .map(move |&offset| longest.start + lap * longest.length + offset)
+        })
+        .filter(|&step| step >= looping)
+        .take(limit)
+        .find(|&step| cycles.iter().all(|cycle| cycle.hits(step)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * A walk along `next`, ignoring directions, with goals at `goals`.
+     */
+    fn cycle(next: &[u32], goals: &[u32]) -> Cycle {
+        analyse(
+            0,
+            next.len(),
+            1,
+            |node, _| next[node as usize],
+            |node| goals.contains(&node),
+        )
+    }
+
+    #[test]
+    fn test_analyse() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 1, with goals 2 and 3.
+        let walk = cycle(&[1, 2, 3, 4, 1], &[2, 3]);
+        assert_eq!(
+            walk,
+            Cycle {
+                entry: (1, 0),
+                start: 1,
+                length: 4,
+                lead_in: vec![],
+                offsets: vec![1, 2],
+            }
+        );
+        assert!(walk.hits(2) && walk.hits(7) && !walk.hits(4));
+        // A goal passed only on the way in.
+        let walk = cycle(&[1, 2, 2], &[0]);
+        assert_eq!((walk.start, walk.length), (2, 1));
+        assert_eq!((walk.lead_in, walk.offsets), (vec![0], vec![]));
+        // The direction index is part of the state.
+        let walk = analyse(0, 1, 2, |_, _| 0, |_| true);
+        assert_eq!((walk.start, walk.length, walk.offsets), (0, 2, vec![0, 1]));
+    }
+
+    #[test]
+    fn test_too_many_residues() {
+        // Every node is a goal, so combining all the congruences would keep
+        // 101 * 103 * 107 residues.
+        let all_goals = |length: u32| {
+            let next: Vec<u32> = (1..length).chain([0]).collect();
+            let goals: Vec<u32> = (0..length).collect();
+            cycle(&next, &goals)
+        };
+        let cycles = [all_goals(101), all_goals(103), all_goals(107)];
+        assert_eq!(earliest_common(&cycles), Some(0));
+        let late = cycle(&[1, 2, 0], &[2]);
+        assert_eq!(
+            earliest_common(&[all_goals(101), all_goals(103), all_goals(107), late]),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_earliest_common() {
+        let first = cycle(&[1, 2, 3, 4, 1], &[2, 3]);
+        let second = cycle(&[1, 2, 3, 1], &[1]);
+        // Goals at 2, 3, 6, 7, 10... and at 1, 4, 7...
+        assert_eq!(earliest_common(&[first.clone(), second]), Some(7));
+        // Met before either walk loops.
+        let lead_in = cycle(&[1, 2, 2], &[0]);
+        assert_eq!(
+            earliest_common(&[lead_in.clone(), cycle(&[0], &[0])]),
+            Some(0)
+        );
+        assert_eq!(earliest_common(&[lead_in, first.clone()]), None);
+        // Goals at 2 and 3 modulo 4 never meet goals at 0 and 1 modulo 4.
+        let other = cycle(&[1, 2, 3, 4, 1], &[0, 4, 1]);
+        assert_eq!(other.offsets, vec![0, 3]);
+        assert_eq!(earliest_common(&[first.clone(), other]), None);
+        assert_eq!(earliest_common(&[first]), Some(2));
+        assert_eq!(earliest_common(&[]), None);
+    }
+}
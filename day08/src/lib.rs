@@ -0,0 +1,333 @@
+pub mod cycle;
+
+use common::parse_chars;
+use common::parse_lines;
+use common::split_once;
+use common::MaybeAnswer;
+use common::ParseError;
+use common::Solution;
+use cycle::Cycle;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+pub enum Direction {
+    Left,
+    Right,
+}
+
+fn parse_direction(c: char) -> Option<Direction> {
+    match c {
+        'L' => Some(Direction::Left),
+        'R' => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+lazy_static! {
+    static ref CONNECTION_REGEX: Regex =
+        Regex::new(r"(\S+) = \((\S+), (\S+)\)").expect("Failed to compile regex");
+}
+
+fn parse_node(line: &str) -> Result<[&str; 3], ParseError> {
+    let captures = CONNECTION_REGEX
+        .captures(line)
+        .ok_or_else(|| ParseError::new(line, line, "expected \"<node> = (<left>, <right>)\""))?;
+    Ok([1, 2, 3].map(|i| captures.get(i).expect("the regex has 3 groups").as_str()))
+}
+
+/**
+ * The directions and the network, with node labels interned as dense ids
+ * so that walking it is just indexing.
+ */
+pub struct Network<'a> {
+    directions: Vec<Direction>,
+    labels: Vec<&'a str>,
+    ids: HashMap<&'a str, u32>,
+    left: Vec<u32>,
+    right: Vec<u32>,
+}
+
+impl<'a> Network<'a> {
+    pub fn parse(input: &'a str) -> Result<Network<'a>, ParseError> {
+        let (directions_part, network_part) = split_once(input, input, "\n\n")?;
+        let directions = parse_chars(directions_part, parse_direction, "invalid direction")?;
+        if directions.is_empty() {
+            return Err(ParseError::new(
+                input,
+                directions_part,
+                "expected directions",
+            ));
+        }
+        let nodes: Vec<[&str; 3]> =
+            parse_lines(network_part, parse_node).map_err(|err| err.within(input, network_part))?;
+        let mut labels = Vec::with_capacity(nodes.len());
+        let mut ids = HashMap::with_capacity(nodes.len());
+        for &[name, _, _] in &nodes {
+            if ids.insert(name, labels.len() as u32).is_some() {
+                return Err(ParseError::new(input, name, "node defined twice"));
+            }
+            labels.push(name);
+        }
+        let id = |label: &str| {
+            ids.get(label)
+                .copied()
+                .ok_or_else(|| ParseError::new(input, label, "unknown node"))
+        };
+        let mut left = Vec::with_capacity(nodes.len());
+        let mut right = Vec::with_capacity(nodes.len());
+        for &[_, left_label, right_label] in &nodes {
+            left.push(id(left_label)?);
+            right.push(id(right_label)?);
+        }
+        Ok(Network {
+            directions,
+            labels,
+            ids,
+            left,
+            right,
+        })
+    }
+
+    pub fn directions(&self) -> &[Direction] {
+        &self.directions
+    }
+
+    pub fn id(&self, label: &str) -> Option<u32> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: u32) -> &'a str {
+        self.labels[id as usize]
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    /**
+     * Every node's id, in the order they were defined.
+     */
+    pub fn nodes(&self) -> impl Iterator<Item = u32> {
+        0..self.labels.len() as u32
+    }
+
+    /**
+     * The node reached from `id` by the direction at index `direction`.
+     */
+    pub fn next(&self, id: u32, direction: usize) -> u32 {
+        match self.directions[direction] {
+            Direction::Left => self.left[id as usize],
+            Direction::Right => self.right[id as usize],
+        }
+    }
+}
+
+/**
+ * Steps from `start` to `goal`, or None if the walk never gets there. The
+ * walk has one state per node and direction index, so if it has not
+ * reached `goal` once every state could have been visited it never will.
+ */
+fn steps_between(network: &Network, start: u32, goal: u32) -> Option<usize> {
+    let directions = network.directions().len();
+    let states = network.node_count() * directions;
+    let mut position = start;
+    for steps in 0..=states {
+        if position == goal {
+            return Some(steps);
+        }
+        position = network.next(position, steps % directions);
+    }
+    None
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+
+    type Input<'a> = Network<'a>;
+    type Part1 = MaybeAnswer<usize>;
+    type Part2 = MaybeAnswer<usize>;
+
+    fn parse(input: &str) -> Result<Network<'_>, ParseError> {
+        Network::parse(input)
+    }
+
+    fn part1(network: &Network) -> MaybeAnswer<usize> {
+        let ends = network.id("AAA").zip(network.id("ZZZ"));
+        MaybeAnswer(ends.and_then(|(start, goal)| steps_between(network, start, goal)))
+    }
+
+    fn part2(network: &Network) -> MaybeAnswer<usize> {
+        let cycles: Vec<Cycle> = network
+            .nodes()
+            .filter(|&id| network.label(id).ends_with('A'))
+            .map(|start| {
+                cycle::analyse(
+                    start,
+                    network.node_count(),
+                    network.directions().len(),
+                    |id, direction| network.next(id, direction),
+                    |id| network.label(id).ends_with('Z'),
+                )
+            })
+            .collect();
+        MaybeAnswer(cycle::earliest_common(&cycles))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day08::part1(
+                &Day08::parse(
+                    "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)"
+                )
+                .unwrap()
+            ),
+            MaybeAnswer(Some(2))
+        );
+
+        assert_eq!(
+            Day08::part1(
+                &Day08::parse(
+                    "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)"
+                )
+                .unwrap()
+            ),
+            MaybeAnswer(Some(6))
+        )
+    }
+
+    #[test]
+    fn test_unsolvable() {
+        let part1 = |input| Day08::part1(&Day08::parse(input).unwrap());
+        assert_eq!(part1("L\n\nAAA = (AAA, AAA)"), MaybeAnswer(None));
+        assert_eq!(part1("L\n\nZZZ = (ZZZ, ZZZ)"), MaybeAnswer(None));
+        assert_eq!(
+            part1("LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, AAA)\nZZZ = (AAA, AAA)"),
+            MaybeAnswer(None)
+        );
+        assert_eq!(
+            part1("L\n\nAAA = (AAA, AAA)\nZZZ = (AAA, AAA)").to_string(),
+            "-"
+        );
+        // No ghosts at all.
+        let network = Day08::parse("L\n\nBBZ = (BBZ, BBZ)");
+        assert_eq!(Day08::part2(&network.unwrap()), MaybeAnswer(None));
+        // The second ghost never reaches a Z node.
+        let network = Day08::parse("L\n\n11A = (11Z, 11Z)\n11Z = (11Z, 11Z)\n22A = (22A, 22A)");
+        assert_eq!(Day08::part2(&network.unwrap()), MaybeAnswer(None));
+    }
+
+    #[test]
+    fn test_network() {
+        let network =
+            Network::parse("LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        let (aaa, bbb, zzz) = (
+            network.id("AAA").unwrap(),
+            network.id("BBB").unwrap(),
+            network.id("ZZZ").unwrap(),
+        );
+        assert_eq!((aaa, bbb, zzz), (0, 1, 2));
+        assert_eq!(network.id("CCC"), None);
+        assert_eq!(network.label(zzz), "ZZZ");
+        assert_eq!((network.next(aaa, 0), network.next(aaa, 1)), (bbb, zzz));
+        assert_eq!(network.nodes().collect::<Vec<u32>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |input| Network::parse(input).err().unwrap();
+        let unknown = err("L\n\nAAA = (BBB, AAA)");
+        assert_eq!((unknown.line, unknown.column), (3, 8));
+        assert_eq!(unknown.message, "unknown node");
+        let twice = err("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)");
+        assert_eq!(
+            (twice.line, twice.message.as_str()),
+            (4, "node defined twice")
+        );
+        assert_eq!(err("\n\nAAA = (AAA, AAA)").message, "expected directions");
+        assert_eq!(err("LX\n\nAAA = (AAA, AAA)").column, 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day08::part2(
+                &Day08::parse(
+                    "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)"
+                )
+                .unwrap()
+            ),
+            MaybeAnswer(Some(6))
+        );
+        // Cycles that do not start at step 0: Z at 2, 5, 8... and at 1, 3, 5...
+        assert_eq!(
+            Day08::part2(
+                &Day08::parse(
+                    "L
+
+11A = (11B, XXX)
+11B = (11Z, XXX)
+11Z = (11C, XXX)
+11C = (11D, XXX)
+11D = (11Z, XXX)
+22A = (22Z, XXX)
+22Z = (22B, XXX)
+22B = (22Z, XXX)
+XXX = (XXX, XXX)"
+                )
+                .unwrap()
+            ),
+            MaybeAnswer(Some(5))
+        );
+        // Two Z nodes in one cycle: Z at 2, 3, 6, 7... and at 1, 4, 7...
+        assert_eq!(
+            Day08::part2(
+                &Day08::parse(
+                    "L
+
+11A = (11B, XXX)
+11B = (11Z, XXX)
+11Z = (12Z, XXX)
+12Z = (11C, XXX)
+11C = (11B, XXX)
+22A = (22Z, XXX)
+22Z = (22B, XXX)
+22B = (22C, XXX)
+22C = (22Z, XXX)
+XXX = (XXX, XXX)"
+                )
+                .unwrap()
+            ),
+            MaybeAnswer(Some(7))
+        )
+    }
+}
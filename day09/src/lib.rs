@@ -0,0 +1,130 @@
+use common::parse_lines;
+use common::parse_number;
+use common::ParseError;
+use common::Solution;
+
+fn parse_line(line: &str) -> Result<Vec<i32>, ParseError> {
+    let history: Vec<i32> = line.split_whitespace()
+        .map(|num_str| parse_number(line, num_str))
+        .collect::<Result<_, _>>()?;
+    if history.is_empty() {
+        return Err(ParseError::new(line, line, "expected a history"));
+    }
+    Ok(history)
+}
+
+fn extrapolate(numbers: &[i32]) -> i32 {
+    let mut input = numbers.to_vec();
+    let mut history: Vec<i32> = Vec::new();
+    loop {
+        let mut differences: Vec<i32> = Vec::with_capacity(input.len() - 1);
+        for (index, &value) in input.iter().enumerate().skip(1) {
+            let previous = input[index - 1];
+            differences.push(value - previous);
+        }
+        history.push(*input.last().unwrap());
+        if differences.iter().all(|&x| x == 0) {
+            break;
+        }
+        input = differences;
+    }
+    history.reverse();
+    let mut diff = 0;
+    for value in history {
+        diff += value;
+    }
+    diff
+}
+
+fn extrapolate_backwards(numbers: &[i32]) -> i32 {
+    let mut input = numbers.to_vec();
+    let mut history: Vec<i32> = Vec::new();
+    loop {
+        let mut differences: Vec<i32> = Vec::with_capacity(input.len() - 1);
+        for (index, &value) in input.iter().enumerate().skip(1) {
+            let previous = input[index - 1];
+            differences.push(previous - value);
+        }
+        history.push(*input.first().unwrap());
+        if differences.iter().all(|&x| x == 0) {
+            break;
+        }
+        input = differences;
+    }
+    history.reverse();
+    let mut diff = 0;
+    for value in history {
+        diff += value;
+    }
+    diff
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+
+    type Input<'a> = Vec<Vec<i32>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+        parse_lines(input, parse_line)
+    }
+
+    fn part1(histories: &Vec<Vec<i32>>) -> i32 {
+        histories.iter().map(|history| extrapolate(history)).sum()
+    }
+
+    fn part2(histories: &Vec<Vec<i32>>) -> i32 {
+        histories.iter().map(|history| extrapolate_backwards(history)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15]), 18);
+        assert_eq!(extrapolate(&[1, 3, 6, 10, 15, 21]), 28);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45]), 68);
+        assert_eq!(extrapolate(&vec!(14, 16, 26, 50, 86, 127, 182, 334, 860, 2447, 6555, 16007, 35930, 75232, 148879, 281333, 511626, 900674, 1541576, 2573792, 4202246)), 6722549);
+        assert_eq!(extrapolate(&vec!(1, 12, 32, 60, 95, 136, 182, 232, 285, 340, 396, 452, 507, 560, 610, 656, 697, 732, 760, 780, 791)), 792)
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day09::parse("1 2 3\n\n4 5 6").unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (2, "expected a history"));
+        assert_eq!(Day09::parse("7").unwrap(), vec!(vec!(7)));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day09::part1(&Day09::parse("0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45").unwrap()),
+            114
+        )
+    }
+
+    #[test]
+    fn test_extrapolate_backwards() {
+        assert_eq!(extrapolate_backwards(&[0, 3, 6, 9, 12, 15]), -3);
+        assert_eq!(extrapolate_backwards(&[1, 3, 6, 10, 15, 21]), 0);
+        assert_eq!(extrapolate_backwards(&[10, 13, 16, 21, 30, 45]), 5);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day09::part2(&Day09::parse("0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45").unwrap()),
+            2
+        )
+    }
+}
@@ -0,0 +1,316 @@
+use common::ParseError;
+use common::Solution;
+use grid::{Coord, Direction, Grid};
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Pipe {
+    NorthSouth,
+    WestEast,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+    Ground,
+    Start, // pipe type is hidden.
+}
+
+fn parse_pipe(c: char) -> Option<Pipe> {
+    match c {
+        '|' => Some(Pipe::NorthSouth),
+        '-' => Some(Pipe::WestEast),
+        'L' => Some(Pipe::NorthEast),
+        'J' => Some(Pipe::NorthWest),
+        '7' => Some(Pipe::SouthWest),
+        'F' => Some(Pipe::SouthEast),
+        '.' => Some(Pipe::Ground),
+        'S' => Some(Pipe::Start),
+        _ => None,
+    }
+}
+
+fn parse_pipes(input: &str) -> Result<Grid<Pipe>, ParseError> {
+    Grid::parse_with(input, parse_pipe, "invalid pipe")
+}
+
+fn calculate_pipe(north: Pipe, east: Pipe, south: Pipe, west: Pipe) -> Option<Pipe> {
+    let north_connected = north == Pipe::SouthEast || north == Pipe::SouthWest || north == Pipe::NorthSouth;
+    let south_connected = south == Pipe::NorthEast || south == Pipe::NorthWest || south == Pipe::NorthSouth;
+    let east_connected = east == Pipe::WestEast || east == Pipe::NorthWest || east == Pipe::SouthWest;
+    let west_connected = west == Pipe::WestEast || west == Pipe::NorthEast || west == Pipe::SouthEast;
+
+    if north_connected && south_connected {
+        Some(Pipe::NorthSouth)
+    } else if north_connected && east_connected {
+        Some(Pipe::NorthEast)
+    } else if north_connected && west_connected {
+        Some(Pipe::NorthWest)
+    } else if south_connected && east_connected {
+        Some(Pipe::SouthEast)
+    } else if south_connected && west_connected {
+        Some(Pipe::SouthWest)
+    } else if west_connected && east_connected {
+        Some(Pipe::WestEast)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug)]
+pub struct Sketch {
+    pipes: Grid<Pipe>,
+    /** Every tile of the loop, starting at the start tile. */
+    path: Vec<Coord>,
+}
+
+/**
+ * An error at the tile at `pos`.
+ */
+fn tile_error(input: &str, pos: Coord, message: &str) -> ParseError {
+    let line = input.lines().nth(pos.y).unwrap_or(input);
+    ParseError::new(line, &line[pos.x..pos.x + 1], message).within(input, line)
+}
+
+/**
+ * Find the start tile and replace it with the pipe it hides.
+ */
+fn find_start(input: &str, pipes: &mut Grid<Pipe>) -> Result<Coord, ParseError> {
+    let start_pos = pipes.position(|&pipe| pipe == Pipe::Start)
+        .ok_or_else(|| ParseError::new(input, &input[input.len()..], "missing start tile 'S'"))?;
+    let neighbour = |dir| pipes.step(start_pos, dir).map_or(Pipe::Ground, |pos| pipes[pos]);
+    let north = neighbour(Direction::Up);
+    let east = neighbour(Direction::Right);
+    let south = neighbour(Direction::Down);
+    let west = neighbour(Direction::Left);
+    let start_pipe = calculate_pipe(north, east, south, west)
+        .ok_or_else(|| tile_error(input, start_pos, "start tile is not on a loop"))?;
+    pipes[start_pos] = start_pipe;
+    Ok(start_pos)
+}
+
+fn parse_grid(input: &str) -> Result<Sketch, ParseError> {
+    let mut pipes = parse_pipes(input)?;
+    let start_pos = find_start(input, &mut pipes)?;
+    let path = trace_loop(&pipes, start_pos)
+        .map_err(|(pos, message)| tile_error(input, pos, message))?;
+    Ok(Sketch { pipes, path })
+}
+
+/**
+ * The two directions a pipe leads in, or None for ground.
+ */
+fn connections(pipe: Pipe) -> Option<(Direction, Direction)> {
+    match pipe {
+        Pipe::NorthSouth => Some((Direction::Up, Direction::Down)),
+        Pipe::WestEast => Some((Direction::Left, Direction::Right)),
+        Pipe::NorthEast => Some((Direction::Up, Direction::Right)),
+        Pipe::NorthWest => Some((Direction::Up, Direction::Left)),
+        Pipe::SouthEast => Some((Direction::Down, Direction::Right)),
+        Pipe::SouthWest => Some((Direction::Down, Direction::Left)),
+        Pipe::Ground | Pipe::Start => None,
+    }
+}
+
+/**
+ * Where a pipe entered from direction `from` leads, or None if it does not
+ * connect that way.
+ */
+fn next_direction(from: Direction, pipe: Pipe) -> Option<Direction> {
+    let (a, b) = connections(pipe)?;
+    if from == a {
+        Some(b)
+    } else if from == b {
+        Some(a)
+    } else {
+        None
+    }
+}
+
+/**
+ * Follow the pipes from the start tile back to it, or report the tile where
+ * the loop breaks.
+ */
+fn trace_loop(pipes: &Grid<Pipe>, start_pos: Coord) -> Result<Vec<Coord>, (Coord, &'static str)> {
+    let (mut dir, back) = connections(pipes[start_pos]).ok_or((start_pos, "start tile is not on a loop"))?;
+    let mut pos = start_pos;
+    let mut path = vec![start_pos];
+    loop {
+        let next = pipes.step(pos, dir).ok_or((pos, "pipe leads off the grid"))?;
+        if next == start_pos {
+            return if dir.reverse() == back {
+                Ok(path)
+            } else {
+                Err((pos, "pipe does not connect"))
+            };
+        }
+        dir = next_direction(dir.reverse(), pipes[next]).ok_or((next, "pipe does not connect"))?;
+        path.push(next);
+        pos = next;
+    }
+}
+
+fn is_inside_polygon(pos: Coord, polygon: &HashSet<Coord>, pipes: &Grid<Pipe>) -> bool {
+    let mut intersections = 0;
+    for x in pos.x..pipes.width() {
+        let test_pos = Coord { x, y: pos.y };
+        if polygon.contains(&test_pos) {
+            let pipe = pipes[test_pos];
+            if pipe == Pipe::NorthSouth || pipe == Pipe::NorthEast || pipe == Pipe::NorthWest {
+                intersections += 1;
+            }
+        }
+    }
+    intersections % 2 == 1
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input<'a> = Sketch;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Sketch, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(grid: &Sketch) -> usize {
+        grid.path.len() / 2
+    }
+
+    fn part2(grid: &Sketch) -> usize {
+        let path_set: HashSet<_> = grid.path.iter().copied().collect();
+        let mut enclosed = 0;
+        for pos in grid.pipes.coords() {
+            if !path_set.contains(&pos) && is_inside_polygon(pos, &path_set, &grid.pipes) {
+                enclosed += 1;
+            }
+        }
+        enclosed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pipes() {
+        assert_eq!(parse_pipes(".....
+.F-7.
+.|.|.
+.L-J.
+....."), Ok(Grid::new(5, 5, vec!(
+            Pipe::Ground, Pipe::Ground, Pipe::Ground, Pipe::Ground, Pipe::Ground,
+            Pipe::Ground, Pipe::SouthEast, Pipe::WestEast, Pipe::SouthWest, Pipe::Ground,
+            Pipe::Ground, Pipe::NorthSouth, Pipe::Ground, Pipe::NorthSouth, Pipe::Ground,
+            Pipe::Ground, Pipe::NorthEast, Pipe::WestEast, Pipe::NorthWest, Pipe::Ground,
+            Pipe::Ground, Pipe::Ground, Pipe::Ground, Pipe::Ground, Pipe::Ground,
+        ))))
+    }
+
+    #[test]
+    fn test_parse_grid_errors() {
+        let err = parse_grid("...\n.X.").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "X"));
+        let err = parse_grid("...\n...").unwrap_err();
+        assert_eq!(err.message, "missing start tile 'S'");
+        let err = parse_grid("...\n.S.\n...").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "S"));
+    }
+
+    #[test]
+    fn test_broken_loop() {
+        let err = parse_grid(".....\n.S-7.\n.|.|.\n.L-..\n.....").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (4, 4, "."));
+        assert_eq!(err.message, "pipe does not connect");
+        let err = parse_grid(".....\n.S-7.\n.|.|.\n.L-F.\n.....").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (4, 4, "F"));
+        let err = parse_grid("S-\n|.").unwrap_err();
+        assert_eq!((err.line, err.column, err.message.as_str()), (2, 1, "pipe leads off the grid"));
+    }
+
+    fn start_pipe(input: &str) -> Pipe {
+        let mut pipes = parse_pipes(input).unwrap();
+        let start_pos = find_start(input, &mut pipes).unwrap();
+        pipes[start_pos]
+    }
+
+    #[test]
+    fn test_calculate_start() {
+        assert_eq!(start_pipe("...
+.|.
+.S.
+.|."), Pipe::NorthSouth);
+        assert_eq!(start_pipe("...
+.|.
+.S-"), Pipe::NorthEast);
+        assert_eq!(start_pipe("...
+.|.
+-S.
+..."), Pipe::NorthWest);
+        assert_eq!(start_pipe("...
+-S.
+.|."), Pipe::SouthWest);
+        assert_eq!(start_pipe("...
+.S-
+.|."), Pipe::SouthEast);
+        assert_eq!(start_pipe("...
+-S-
+..."), Pipe::WestEast);
+        assert_eq!(start_pipe(".....
+.S-7.
+.|.|.
+.L-J.
+....."), Pipe::SouthEast);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day10::part1(&Day10::parse("7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ").unwrap()),
+            8
+        )
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day10::part2(&Day10::parse("...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........").unwrap()), 4);
+
+        assert_eq!(Day10::part2(&Day10::parse(".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...").unwrap()), 8);
+
+        assert_eq!(Day10::part2(&Day10::parse("FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L").unwrap()), 10);
+    }
+}
@@ -0,0 +1,271 @@
+use common::ParseError;
+use common::Solution;
+use grid::{Coord, Grid};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Row {
+    Expand,
+    Regular(Vec<Cell>),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    Expand,
+    Galaxy,
+}
+
+fn parse_universe(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse_with(
+        input,
+        |c| matches!(c, '.' | '#').then_some(c),
+        "invalid space",
+    )
+}
+
+fn expand_universe(map: &Grid<char>) -> Vec<Row> {
+    let cols = map.width();
+    // Pass 1. Determine empty columns.
+    let empty_columns: Vec<bool> = map
+        .columns()
+        .map(|mut column| column.all(|&c| c == '.'))
+        .collect();
+    // Pass 2. Now we can expand the universe.
+    let mut universe: Vec<Row> = Vec::with_capacity(cols * 2);
+    for row in map.rows() {
+        let mut empty_row = true;
+        let mut universe_row: Vec<Cell> = Vec::with_capacity(cols * 2);
+        for (x, &c) in row.iter().enumerate() {
+            if empty_columns[x] {
+                universe_row.push(Cell::Expand);
+            } else {
+                if c != '.' {
+                    empty_row = false;
+                    universe_row.push(Cell::Galaxy);
+                } else {
+                    universe_row.push(Cell::Empty);
+                }
+            }
+        }
+        if empty_row {
+            universe.push(Row::Expand);
+        } else {
+            universe.push(Row::Regular(universe_row));
+        }
+    }
+    universe
+}
+
+fn get_galaxies(map: &Grid<char>, expand_size: usize) -> Vec<Coord> {
+    let universe = expand_universe(map);
+    let mut galaxies: Vec<Coord> = Vec::new();
+    let mut y = 0;
+    for row in universe {
+        match row {
+            Row::Expand => y += expand_size,
+            Row::Regular(cells) => {
+                let mut x = 0;
+                for cell in cells {
+                    match cell {
+                        Cell::Empty => x += 1,
+                        Cell::Expand => x += expand_size,
+                        Cell::Galaxy => {
+                            galaxies.push(Coord { x, y });
+                            x += 1;
+                        }
+                    }
+                }
+                y += 1;
+            }
+        }
+    }
+    galaxies
+}
+
+fn combinations(coords: &[Coord]) -> Vec<(Coord, Coord)> {
+    let mut combinations = Vec::new();
+    for i in 0..coords.len() {
+        for j in i + 1..coords.len() {
+            combinations.push((coords[i], coords[j]));
+        }
+    }
+    combinations
+}
+
+fn sum_shortest_paths(map: &Grid<char>, expand_size: usize) -> usize {
+    let galaxies = get_galaxies(map, expand_size);
+    let mut total = 0;
+    for (a, b) in combinations(&galaxies) {
+        total += a.manhattan(b);
+    }
+    total
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input<'a> = Grid<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+        parse_universe(input)
+    }
+
+    fn part1(map: &Grid<char>) -> usize {
+        sum_shortest_paths(map, 2)
+    }
+
+    fn part2(map: &Grid<char>) -> usize {
+        sum_shortest_paths(map, 1000000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_expanded(input: &str) -> Vec<Row> {
+        input
+            .lines()
+            .map(|line| {
+                if line == ">" {
+                    Row::Expand
+                } else {
+                    Row::Regular(
+                        line.chars()
+                            .map(|c| match c {
+                                'v' => Cell::Expand,
+                                '.' => Cell::Empty,
+                                _ => Cell::Galaxy,
+                            })
+                            .collect(),
+                    )
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_expand() {
+        let map = parse_universe(
+            "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....",
+        )
+        .unwrap();
+        let expected = parse_expanded(
+            "..v#.v..v.
+..v..v.#v.
+#.v..v..v.
+>
+..v..v#.v.
+.#v..v..v.
+..v..v..v#
+>
+..v..v.#v.
+#.v.#v..v.",
+        );
+        let expanded = expand_universe(&map);
+        assert_eq!(expanded, expected);
+    }
+
+    fn find_galaxies(map: &Grid<char>) -> Vec<Coord> {
+        map.iter()
+            .filter(|&(_, &c)| c != '.')
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+
+    #[test]
+    fn test_get_galaxies() {
+        let expected = find_galaxies(
+            &parse_universe(
+                "....#........
+.........#...
+#............
+.............
+.............
+........#....
+.#...........
+............#
+.............
+.............
+.........#...
+#....#.......",
+            )
+            .unwrap(),
+        );
+        let actual = get_galaxies(
+            &parse_universe(
+                "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....",
+            )
+            .unwrap(),
+            2,
+        );
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day11::part1(
+                &Day11::parse(
+                    "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#....."
+                )
+                .unwrap()
+            ),
+            374
+        )
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            sum_shortest_paths(
+                &parse_universe(
+                    "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#....."
+                )
+                .unwrap(),
+                10
+            ),
+            1030
+        )
+    }
+}
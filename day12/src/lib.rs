@@ -0,0 +1,221 @@
+use common::parse_chars;
+use common::parse_lines;
+use common::parse_number;
+use common::split_once;
+use common::ParseError;
+use common::Solution;
+use memoize::memoize;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+enum Condition {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct Row {
+    conditions: Vec<Condition>,
+    group_sizes: Vec<usize>,
+}
+
+impl fmt::Display for Row {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut result = String::new();
+        for c in &self.conditions {
+            match c {
+                Condition::Damaged => result.push('#'),
+                Condition::Operational => result.push('.'),
+                Condition::Unknown => result.push('?'),
+            }
+        }
+        result.push(' ');
+        for (idx, group_size) in self.group_sizes.iter().enumerate() {
+            if idx > 0 {
+                result.push(',');
+            }
+            result.push_str(group_size.to_string().as_str());
+        }
+        write!(f, "{}", result)
+    }
+}
+
+fn parse_condition(c: char) -> Option<Condition> {
+    match c {
+        '.' => Some(Condition::Operational),
+        '#' => Some(Condition::Damaged),
+        '?' => Some(Condition::Unknown),
+        _ => None,
+    }
+}
+
+fn parse_line(line: &str) -> Result<Row, ParseError> {
+    let (conditions_part, group_sizes_part) = split_once(line, line, " ")?;
+    let conditions: Vec<Condition> =
+        parse_chars(conditions_part, parse_condition, "invalid condition")?;
+    let group_sizes: Vec<usize> = group_sizes_part
+        .split(',')
+        .map(|num_str| parse_number(line, num_str))
+        .collect::<Result<_, _>>()?;
+    Ok(Row {
+        conditions,
+        group_sizes,
+    })
+}
+
+/**
+ * The possible combinations after match first group.
+ */
+fn after_group_combinations(conditions: Vec<Condition>, group_sizes: Vec<usize>) -> usize {
+    let group_size = group_sizes[0];
+    if conditions.len() >= group_size
+        && conditions[..group_size]
+            .iter()
+            .all(|c| *c == Condition::Damaged || *c == Condition::Unknown)
+    {
+        let new_conditions = conditions[group_size..].to_vec();
+        let new_group_sizes = group_sizes[1..].to_vec();
+        if !new_conditions.is_empty() {
+            if new_conditions[0] == Condition::Operational
+                || new_conditions[0] == Condition::Unknown
+            {
+                combinations(new_conditions[1..].to_vec(), new_group_sizes)
+            } else {
+                0
+            }
+        } else {
+            combinations(new_conditions, new_group_sizes)
+        }
+    } else {
+        0
+    }
+}
+
+#[memoize]
+fn combinations(conditions: Vec<Condition>, group_sizes: Vec<usize>) -> usize {
+    if group_sizes.is_empty() {
+        if conditions.is_empty() {
+            // Valid combination: all groups processed with no remaining input.
+            1
+        } else {
+            if conditions
+                .iter()
+                .all(|c| *c == Condition::Operational || *c == Condition::Unknown)
+            {
+                // Valid combination: all groups processed so all remaining
+                // Unknowns are Operational.
+                1
+            } else {
+                // Invalid combination: No groups left but there are still
+                // Damaged groups.
+                0
+            }
+        }
+    } else if conditions.is_empty() {
+        // Invalid combination: There no input left but still have remaining
+        // Damaged groups.
+        0
+    } else {
+        match conditions[0] {
+            Condition::Operational => {
+                let new_conditions = conditions
+                    .iter()
+                    .skip_while(|c| **c == Condition::Operational)
+                    .copied()
+                    .collect();
+                combinations(new_conditions, group_sizes)
+            }
+            Condition::Damaged => after_group_combinations(conditions, group_sizes),
+            Condition::Unknown => {
+                let new_conditions = conditions
+                    .iter()
+                    .skip(1) // skip Unknown
+                    .skip_while(|c| **c == Condition::Operational)
+                    .copied()
+                    .collect();
+                // sum of both when Unknown is Operational and Damaged
+                combinations(new_conditions, group_sizes.clone())
+                    + after_group_combinations(conditions, group_sizes)
+            }
+        }
+    }
+}
+
+fn total_combinations(rows: &[Row]) -> usize {
+    let mut total = 0;
+    for row in rows {
+        total += combinations(row.conditions.clone(), row.group_sizes.clone());
+    }
+    total
+}
+
+fn unfold(folded: &Row) -> Row {
+    Row {
+        conditions: (0..4)
+            .flat_map(|_| {
+                folded
+                    .conditions
+                    .iter()
+                    .cloned()
+                    .chain(vec![Condition::Unknown])
+            })
+            .chain(folded.conditions.clone())
+            .collect(),
+        group_sizes: (0..5).flat_map(|_| folded.group_sizes.clone()).collect(),
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input<'a> = Vec<Row>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Row>, ParseError> {
+        parse_lines(input, parse_line)
+    }
+
+    fn part1(rows: &Vec<Row>) -> usize {
+        total_combinations(rows)
+    }
+
+    fn part2(rows: &Vec<Row>) -> usize {
+        let unfolded: Vec<Row> = rows.iter().map(unfold).collect();
+        total_combinations(&unfolded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day12::part1(&Day12::parse(SAMPLE).unwrap()), 21)
+    }
+
+    #[test]
+    fn test_unfold() {
+        let row = parse_line("???.### 1,1,3").unwrap();
+        assert_eq!(
+            unfold(&row).to_string().as_str(),
+            "???.###????.###????.###????.###????.### 1,1,3,1,1,3,1,1,3,1,1,3,1,1,3"
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day12::part2(&Day12::parse(SAMPLE).unwrap()), 525152)
+    }
+}
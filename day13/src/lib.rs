@@ -0,0 +1,199 @@
+use common::MaybeAnswer;
+use common::ParseError;
+use common::Solution;
+use grid::Grid;
+
+fn check_mirror(lines: &[&[char]], split_at: usize) -> bool {
+    let (first, second) = lines.split_at(split_at);
+    let n = std::cmp::min(first.len(), second.len());
+    let mirror_first: Vec<&[char]> = first.iter().rev().cloned().take(n).collect();
+    let mirror_second: Vec<&[char]> = second.iter().cloned().take(n).collect();
+    mirror_first == mirror_second
+}
+
+/**
+ * 100 times the rows above a horizontal line of reflection, or the columns
+ * left of a vertical one, or None if the pattern has neither.
+ */
+fn find_reflection(pattern: &Grid<char>) -> Option<usize> {
+    let lines: Vec<&[char]> = pattern.rows().collect();
+    for (row, window) in (1..).zip(lines.windows(2)) {
+        if window[0] == window[1] && check_mirror(&lines, row) {
+            return Some(row * 100);
+        }
+    }
+    let transposed = pattern.transpose();
+    let columns: Vec<&[char]> = transposed.rows().collect();
+    for (col, window) in (1..).zip(columns.windows(2)) {
+        if window[0] == window[1] && check_mirror(&columns, col) {
+            return Some(col);
+        }
+    }
+    None
+}
+
+fn compare(a: &[char], b: &[char]) -> usize {
+    a.iter().zip(b.iter()).filter(|&(c1, c2)| c1 != c2).count()
+}
+
+fn check_mirror_with_smudge(lines: &[&[char]], split_at: usize) -> bool {
+    let (first, second) = lines.split_at(split_at);
+    let n = std::cmp::min(first.len(), second.len());
+    let mirror_first: Vec<&[char]> = first.iter().rev().cloned().take(n).collect();
+    let mirror_second: Vec<&[char]> = second.iter().cloned().take(n).collect();
+    let mut smudges = 0;
+    for i in 0..n {
+        if smudges > 1 {
+            return false;
+        }
+        let a = mirror_first[i];
+        let b = mirror_second[i];
+        smudges += compare(a, b);
+    }
+    smudges == 1
+}
+
+/**
+ * As [`find_reflection`], for the line that needs exactly one smudge fixed.
+ */
+fn find_reflection2(pattern: &Grid<char>) -> Option<usize> {
+    let lines: Vec<&[char]> = pattern.rows().collect();
+    for row in 1..lines.len() {
+        if check_mirror_with_smudge(&lines, row) {
+            return Some(row * 100);
+        }
+    }
+    let transposed = pattern.transpose();
+    let columns: Vec<&[char]> = transposed.rows().collect();
+    (1..columns.len()).find(|&col| check_mirror_with_smudge(&columns, col))
+}
+
+/**
+ * Parse a pattern, checking that it is a non-empty rectangle of ash and rocks.
+ */
+fn parse_pattern(pattern: &str) -> Result<Grid<char>, ParseError> {
+    if pattern.lines().next().is_none_or(str::is_empty) {
+        return Err(ParseError::new(pattern, pattern, "empty pattern"));
+    }
+    Grid::parse_with(
+        pattern,
+        |c| matches!(c, '.' | '#').then_some(c),
+        "invalid ground",
+    )
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+
+    type Input<'a> = Vec<Grid<char>>;
+    type Part1 = MaybeAnswer<usize>;
+    type Part2 = MaybeAnswer<usize>;
+
+    fn parse(input: &str) -> Result<Vec<Grid<char>>, ParseError> {
+        input
+            .split("\n\n")
+            .map(|pattern| parse_pattern(pattern).map_err(|err| err.within(input, pattern)))
+            .collect()
+    }
+
+    fn part1(patterns: &Vec<Grid<char>>) -> MaybeAnswer<usize> {
+        MaybeAnswer(patterns.iter().map(find_reflection).sum())
+    }
+
+    fn part2(patterns: &Vec<Grid<char>>) -> MaybeAnswer<usize> {
+        MaybeAnswer(patterns.iter().map(find_reflection2).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn test_reflection() {
+        assert_eq!(
+            find_reflection(
+                &parse_pattern(
+                    "###.##.######
+....##.......
+##..##..####.
+..#.##.......
+..#....#....#
+#........##..
+###....######
+..#....#....#
+.#.####.#..#.
+#.##..##.##.#
+.#.####.#..#.
+.########..##
+#.##..##.##.#"
+                )
+                .unwrap()
+            ),
+            Some(10)
+        );
+        assert_eq!(find_reflection(&parse_pattern("#.\n..").unwrap()), None);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day13::part1(&Day13::parse(SAMPLE).unwrap()),
+            MaybeAnswer(Some(405))
+        );
+        assert_eq!(
+            Day13::part1(&Day13::parse("#.\n..").unwrap()),
+            MaybeAnswer(None)
+        )
+    }
+
+    #[test]
+    fn test_reflection2() {
+        assert_eq!(
+            find_reflection2(
+                &parse_pattern(
+                    "###....####..#.##
+##.#..#.#####.###
+....##....###..##
+...#..#....#.##..
+.########..#.....
+..######...######
+.#..##..#..#.#..#"
+                )
+                .unwrap()
+            ),
+            Some(16)
+        );
+        assert_eq!(
+            find_reflection2(&parse_pattern("#.\n##").unwrap()),
+            Some(100)
+        );
+        assert_eq!(find_reflection2(&parse_pattern("##\n..").unwrap()), None);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day13::part2(&Day13::parse(SAMPLE).unwrap()),
+            MaybeAnswer(Some(400))
+        )
+    }
+}
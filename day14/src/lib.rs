@@ -0,0 +1,236 @@
+use common::ParseError;
+use common::Solution;
+use grid::{Coord, Grid};
+use std::collections::{HashSet, hash_map::DefaultHasher};
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Tile {
+    Round,
+    Cube,
+    Empty,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
+        match c {
+            'O' => Ok(Tile::Round),
+            '#' => Ok(Tile::Cube),
+            '.' => Ok(Tile::Empty),
+            t => Err(t),
+        }
+    }
+}
+
+impl From<Tile> for char {
+    fn from(val: Tile) -> Self {
+        match val {
+            Tile::Round => 'O',
+            Tile::Cube => '#',
+            Tile::Empty => '.',
+        }
+    }
+}
+
+fn parse_grid(input: &str) -> Result<Grid<Tile>, ParseError> {
+    Grid::parse_with(input, |c| Tile::try_from(c).ok(), "invalid tile")
+}
+
+fn tilt_north(grid: &mut Grid<Tile>) {
+    for y in 1..grid.height() {
+        for x in 0..grid.width() {
+            if grid[Coord::new(x, y)] == Tile::Round {
+                let mut new_y = y;
+                while new_y > 0 {
+                    let above = grid[Coord::new(x, new_y - 1)];
+                    if above == Tile::Cube || above == Tile::Round {
+                        break;
+                    }
+                    new_y -= 1;
+                }
+                if new_y != y {
+                    grid[Coord::new(x, y)] = Tile::Empty;
+                    grid[Coord::new(x, new_y)] = Tile::Round;
+                }
+            }
+        }
+    }
+}
+
+fn score(grid: &Grid<Tile>) -> usize {
+    let rows = grid.height();
+    let mut score = 0;
+    for (y, row) in grid.rows().enumerate() {
+        let per_rock = rows - y;
+        let round_count = row.iter().filter(|t| **t == Tile::Round).count();
+        score += round_count * per_rock;
+    }
+    score
+}
+
+/**
+ * Tilt north, west, south and east in turn. Rotating the grid clockwise after
+ * each tilt brings the next side to the top, and four rotations restore the
+ * original orientation.
+ */
+fn tilt_cycle(grid: &mut Grid<Tile>) {
+    for _ in 0..4 {
+        tilt_north(grid);
+        *grid = grid.rotate_clockwise();
+    }
+}
+
+fn hash_grid(grid: &Grid<Tile>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    grid.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+
+    type Input<'a> = Grid<Tile>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(tiles: &Grid<Tile>) -> usize {
+        let mut grid = tiles.clone();
+        tilt_north(&mut grid);
+        score(&grid)
+    }
+
+    fn part2(tiles: &Grid<Tile>) -> usize {
+        let mut grid = tiles.clone();
+        let mut steps = 0;
+        let mut cycle = 0;
+        for _ in 0..3 {
+            let mut hash_set: HashSet<u64> = HashSet::new();
+            cycle = 0;
+            loop {
+                let hash = hash_grid(&grid);
+                if hash_set.contains(&hash) {
+                    break;
+                }
+                hash_set.insert(hash);
+                tilt_cycle(&mut grid);
+                steps += 1;
+                cycle += 1;
+            }
+        }
+        let mut remaining = 1_000_000_000 - steps;
+        let repeats = remaining / cycle;
+        remaining -= repeats * cycle;
+        for _ in 0..remaining {
+            tilt_cycle(&mut grid);
+        }
+        score(&grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn test_tilt() {
+        let mut grid = parse_grid(SAMPLE).unwrap();
+        tilt_north(&mut grid);
+        assert_eq!(
+            grid,
+            parse_grid(
+                "OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#...."
+            ).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day14::part1(&Day14::parse(SAMPLE).unwrap()), 136)
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut grid = parse_grid(SAMPLE).unwrap();
+        tilt_cycle(&mut grid);
+        assert_eq!(
+            grid,
+            parse_grid(
+                ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#...."
+            ).unwrap()
+        );
+        tilt_cycle(&mut grid);
+        assert_eq!(
+            grid,
+            parse_grid(
+                ".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#..OO###..
+#.OOO#...O"
+            ).unwrap()
+        );
+        tilt_cycle(&mut grid);
+        assert_eq!(
+            grid,
+            parse_grid(
+                ".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O"
+            ).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day14::part2(&Day14::parse(SAMPLE).unwrap()), 64)
+    }
+}
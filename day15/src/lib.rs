@@ -0,0 +1,136 @@
+use common::parse_number;
+use common::ParseError;
+use common::Solution;
+
+fn hash(s: &str) -> usize {
+    let mut value: usize = 0;
+    for c in s.chars() {
+        value += c as usize;
+        value *= 17;
+        value %= 256;
+    }
+    value
+}
+
+pub enum Operation {
+    Remove,
+    Insert(usize),
+}
+
+/**
+ * One step of the initialization sequence. Part 1 hashes the raw text, part 2
+ * applies the operation to the lens labelled `label`.
+ */
+pub struct Step<'a> {
+    text: &'a str,
+    label: &'a str,
+    operation: Operation,
+}
+
+fn parse_step<'a>(input: &str, text: &'a str) -> Result<Step<'a>, ParseError> {
+    let (label, operation) = if let Some(label) = text.strip_suffix('-') {
+        (label, Operation::Remove)
+    } else if let Some((label, focal_len)) = text.split_once('=') {
+        (label, Operation::Insert(parse_number(input, focal_len)?))
+    } else {
+        return Err(ParseError::new(input, text, "expected \"-\" or \"=\""));
+    };
+    Ok(Step {
+        text,
+        label,
+        operation,
+    })
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+
+    type Input<'a> = Vec<Step<'a>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Step<'_>>, ParseError> {
+        input
+            .trim()
+            .split(',')
+            .map(|text| parse_step(input, text))
+            .collect()
+    }
+
+    fn part1(steps: &Vec<Step<'_>>) -> usize {
+        steps.iter().map(|step| hash(step.text)).sum()
+    }
+
+    fn part2(steps: &Vec<Step<'_>>) -> usize {
+        let mut boxes: Vec<Vec<(&str, usize)>> = vec![vec![]; 256];
+        for step in steps {
+            let label = step.label;
+            let box_idx = hash(label);
+            let position = boxes[box_idx]
+                .iter()
+                .position(|&(len_label, _)| len_label == label);
+            match (&step.operation, position) {
+                (Operation::Remove, Some(index)) => {
+                    boxes[box_idx].remove(index);
+                }
+                (Operation::Remove, None) => {}
+                (&Operation::Insert(focal_len), Some(index)) => {
+                    boxes[box_idx][index] = (label, focal_len);
+                }
+                (&Operation::Insert(focal_len), None) => {
+                    boxes[box_idx].push((label, focal_len));
+                }
+            }
+        }
+        let mut focusing_power = 0;
+        for (box_idx, b) in boxes.iter().enumerate() {
+            for (slot_idx, (_, focal_len)) in b.iter().enumerate() {
+                focusing_power += (box_idx + 1) * (slot_idx + 1) * focal_len;
+            }
+        }
+        focusing_power
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("HASH"), 52);
+        assert_eq!(hash("rn=1"), 30);
+        assert_eq!(hash("cm-"), 253);
+        assert_eq!(hash("qp=3"), 97);
+        assert_eq!(hash("cm=2"), 47);
+        assert_eq!(hash("qp-"), 14);
+        assert_eq!(hash("pc=4"), 180);
+        assert_eq!(hash("ot=9"), 9);
+        assert_eq!(hash("ab=5"), 197);
+        assert_eq!(hash("pc-"), 48);
+        assert_eq!(hash("pc=6"), 214);
+        assert_eq!(hash("ot=7"), 231);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day15::parse("rn=1,cm-,qp=x").err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (13, "x"));
+        let err = Day15::parse("rn=1,cm").err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (6, "cm"));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day15::part1(&Day15::parse(SAMPLE).unwrap()), 1320)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day15::part2(&Day15::parse(SAMPLE).unwrap()), 145)
+    }
+}
@@ -0,0 +1,247 @@
+use common::ParseError;
+use common::Solution;
+use grid::{Coord, Direction, Grid};
+use std::collections::HashSet;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+struct BeamPath {
+    pos: Coord,
+    direction: Direction,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Tile {
+    Empty,
+    Forward,
+    Backward,
+    Vertical,
+    Horizontal,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
+        match c {
+            '.' => Ok(Tile::Empty),
+            '/' => Ok(Tile::Forward),
+            '\\' => Ok(Tile::Backward),
+            '|' => Ok(Tile::Vertical),
+            '-' => Ok(Tile::Horizontal),
+            t => Err(t),
+        }
+    }
+}
+
+impl From<Tile> for char {
+    fn from(val: Tile) -> Self {
+        match val {
+            Tile::Empty => '.',
+            Tile::Forward => '/',
+            Tile::Backward => '\\',
+            Tile::Vertical => '|',
+            Tile::Horizontal => '-',
+        }
+    }
+}
+
+fn parse_layout(input: &str) -> Result<Grid<Tile>, ParseError> {
+    let layout = Grid::parse_with(input, |c| Tile::try_from(c).ok(), "invalid tile")?;
+    if layout.is_empty() {
+        return Err(ParseError::new(input, input, "empty grid"));
+    }
+    Ok(layout)
+}
+
+fn get_beam_direction(input: Direction, tile: Tile) -> Vec<Direction> {
+    let mut outputs: Vec<Direction> = Vec::with_capacity(2);
+    match (input, tile) {
+        (dir, Tile::Empty) => outputs.push(dir),
+        (Direction::Right, Tile::Forward) => outputs.push(Direction::Up),
+        (Direction::Right, Tile::Backward) => outputs.push(Direction::Down),
+        (Direction::Right, Tile::Horizontal) => outputs.push(Direction::Right),
+        (Direction::Left, Tile::Forward) => outputs.push(Direction::Down),
+        (Direction::Left, Tile::Backward) => outputs.push(Direction::Up),
+        (Direction::Left, Tile::Horizontal) => outputs.push(Direction::Left),
+        (Direction::Down, Tile::Forward) => outputs.push(Direction::Left),
+        (Direction::Down, Tile::Backward) => outputs.push(Direction::Right),
+        (Direction::Down, Tile::Vertical) => outputs.push(Direction::Down),
+        (Direction::Up, Tile::Forward) => outputs.push(Direction::Right),
+        (Direction::Up, Tile::Backward) => outputs.push(Direction::Left),
+        (Direction::Up, Tile::Vertical) => outputs.push(Direction::Up),
+        (Direction::Right, Tile::Vertical) => {
+            outputs.push(Direction::Up);
+            outputs.push(Direction::Down);
+        }
+        (Direction::Left, Tile::Vertical) => {
+            outputs.push(Direction::Up);
+            outputs.push(Direction::Down);
+        }
+        (Direction::Down, Tile::Horizontal) => {
+            outputs.push(Direction::Right);
+            outputs.push(Direction::Left);
+        }
+        (Direction::Up, Tile::Horizontal) => {
+            outputs.push(Direction::Right);
+            outputs.push(Direction::Left);
+        }
+    }
+    outputs
+}
+
+fn calc_energized(start: BeamPath, layout: &Grid<Tile>) -> usize {
+    let mut to_visit: Vec<BeamPath> = Vec::new();
+    for dir in get_beam_direction(start.direction, layout[start.pos]) {
+        to_visit.push(BeamPath {
+            pos: start.pos,
+            direction: dir,
+        });
+    }
+    let mut visited: HashSet<Coord> = HashSet::new();
+    let mut seen: HashSet<BeamPath> = HashSet::new();
+    while let Some(bp) = to_visit.pop() {
+        if seen.contains(&bp) {
+            continue;
+        }
+        seen.insert(bp);
+        visited.insert(bp.pos);
+        if let Some(new_pos) = layout.step(bp.pos, bp.direction) {
+            let tile = layout[new_pos];
+            for dir in get_beam_direction(bp.direction, tile) {
+                to_visit.push(BeamPath {
+                    pos: new_pos,
+                    direction: dir,
+                });
+            }
+        }
+    }
+    visited.len()
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+
+    type Input<'a> = Grid<Tile>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+        parse_layout(input)
+    }
+
+    fn part1(layout: &Grid<Tile>) -> usize {
+        let start = BeamPath {
+            pos: Coord { x: 0, y: 0 },
+            direction: Direction::Right,
+        };
+        calc_energized(start, layout)
+    }
+
+    fn part2(layout: &Grid<Tile>) -> usize {
+        let rows = layout.height();
+        let cols = layout.width();
+        let mut max_energy = 0;
+        let mut possible_starts: Vec<BeamPath> =
+            Vec::with_capacity((rows + 1) * 2 + (cols + 1) * 2);
+        possible_starts.push(BeamPath {
+            pos: Coord { x: 0, y: 0 },
+            direction: Direction::Right,
+        });
+        possible_starts.push(BeamPath {
+            pos: Coord { x: 0, y: 0 },
+            direction: Direction::Down,
+        });
+        possible_starts.push(BeamPath {
+            pos: Coord { x: cols - 1, y: 0 },
+            direction: Direction::Left,
+        });
+        possible_starts.push(BeamPath {
+            pos: Coord { x: cols - 1, y: 0 },
+            direction: Direction::Down,
+        });
+        possible_starts.push(BeamPath {
+            pos: Coord { x: 0, y: rows - 1 },
+            direction: Direction::Right,
+        });
+        possible_starts.push(BeamPath {
+            pos: Coord { x: 0, y: rows - 1 },
+            direction: Direction::Up,
+        });
+        possible_starts.push(BeamPath {
+            pos: Coord {
+                x: cols - 1,
+                y: rows - 1,
+            },
+            direction: Direction::Left,
+        });
+        possible_starts.push(BeamPath {
+            pos: Coord {
+                x: cols - 1,
+                y: rows - 1,
+            },
+            direction: Direction::Up,
+        });
+        for x in 1..cols - 1 {
+            possible_starts.push(BeamPath {
+                pos: Coord { x, y: 0 },
+                direction: Direction::Down,
+            });
+            possible_starts.push(BeamPath {
+                pos: Coord { x, y: rows - 1 },
+                direction: Direction::Up,
+            });
+        }
+        for y in 1..rows - 1 {
+            possible_starts.push(BeamPath {
+                pos: Coord { x: 0, y },
+                direction: Direction::Right,
+            });
+            possible_starts.push(BeamPath {
+                pos: Coord { x: cols - 1, y },
+                direction: Direction::Left,
+            });
+        }
+        for start in possible_starts {
+            let energy = calc_energized(start, layout);
+            if energy > max_energy {
+                max_energy = energy;
+            }
+        }
+        max_energy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day16::part1(&Day16::parse(SAMPLE).unwrap()), 46)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day16::part2(&Day16::parse(SAMPLE).unwrap()), 51)
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Day16::parse("").unwrap_err().message, "empty grid");
+        assert_eq!(Day16::parse("\n").unwrap_err().message, "empty grid");
+        assert_eq!(Day16::parse(".|\n.x").unwrap_err().column, 2);
+    }
+}
@@ -0,0 +1,158 @@
+use common::MaybeAnswer;
+use common::ParseError;
+use common::Solution;
+use grid::{Coord, Direction, Grid};
+
+fn parse_grid(input: &str) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::parse_with(
+        input,
+        |c| c.to_digit(10).map(|d| d as u8),
+        "invalid heat loss",
+    )?;
+    if grid.is_empty() {
+        return Err(ParseError::new(input, input, "empty grid"));
+    }
+    Ok(grid)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct PathNode {
+    pos: Coord,
+    dir: Option<Direction>,
+}
+
+/**
+ * Every node reachable from `node` by turning and then moving between
+ * `min_step` and `max_step` blocks, with the heat lost on the way.
+ */
+fn moves(
+    grid: &Grid<u8>,
+    node: &PathNode,
+    min_step: isize,
+    max_step: isize,
+) -> Vec<(PathNode, usize)> {
+    let mut moves = Vec::new();
+    for d in Direction::ALL {
+        if node.dir == Some(d) || node.dir == Some(d.reverse()) {
+            continue;
+        }
+        let (dx, dy) = d.delta();
+        let mut cost = 0;
+        for dist in 1..=max_step {
+            let Some(next) = node
+                .pos
+                .offset((dx * dist, dy * dist))
+                .filter(|&c| grid.contains(c))
+            else {
+                break;
+            };
+            cost += grid[next] as usize;
+            if min_step <= dist {
+                let next = PathNode {
+                    pos: next,
+                    dir: Some(d),
+                };
+                moves.push((next, cost));
+            }
+        }
+    }
+    moves
+}
+
+/**
+ * Least heat lost on the way to the bottom-right block, or None if it
+ * cannot be reached in moves of `min_step` to `max_step` blocks.
+ */
+fn least_heat_loss(grid: &Grid<u8>, min_step: isize, max_step: isize) -> Option<usize> {
+    let goal = Coord::new(grid.width() - 1, grid.height() - 1);
+    let start = PathNode {
+        pos: Coord::new(0, 0),
+        dir: None,
+    };
+    graph::dijkstra(
+        start,
+        |node| moves(grid, node, min_step, max_step),
+        |node| node.pos == goal,
+    )
+    .map(|path| path.cost)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+
+    type Input<'a> = Grid<u8>;
+    type Part1 = MaybeAnswer<usize>;
+    type Part2 = MaybeAnswer<usize>;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(grid: &Grid<u8>) -> MaybeAnswer<usize> {
+        MaybeAnswer(least_heat_loss(grid, 1, 3))
+    }
+
+    fn part2(grid: &Grid<u8>) -> MaybeAnswer<usize> {
+        MaybeAnswer(least_heat_loss(grid, 4, 10))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day17::part1(&Day17::parse(SAMPLE).unwrap()),
+            MaybeAnswer(Some(102))
+        )
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day17::part2(&Day17::parse(SAMPLE).unwrap()),
+            MaybeAnswer(Some(94))
+        );
+        assert_eq!(
+            Day17::part2(
+                &Day17::parse(
+                    "111111111111
+999999999991
+999999999991
+999999999991
+999999999991"
+                )
+                .unwrap()
+            ),
+            MaybeAnswer(Some(71))
+        );
+        // Moves of at least four blocks cannot reach the corner of a 2x2 city.
+        let small = Day17::parse("11\n11").unwrap();
+        assert_eq!(Day17::part1(&small), MaybeAnswer(Some(2)));
+        assert_eq!(Day17::part2(&small), MaybeAnswer(None));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Day17::parse("").unwrap_err().message, "empty grid");
+        assert_eq!(Day17::parse("12\n3").unwrap_err().line, 2);
+    }
+}
@@ -0,0 +1,162 @@
+use common::parse_lines;
+use common::parse_number;
+use common::ParseError;
+use common::Solution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+#[derive(Debug)]
+struct Op {
+    direction: Direction,
+    length: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+fn polygon_create(ops: &[Op]) -> Vec<Point> {
+    let mut pos = Point { x: 0, y: 0 }; // Start at origin.
+    let mut vertices: Vec<Point> = Vec::with_capacity(ops.len() + 1);
+    vertices.push(pos);
+    for op in ops {
+        match op.direction {
+            Direction::Right => pos.x += op.length,
+            Direction::Down => pos.y += op.length,
+            Direction::Left => pos.x -= op.length,
+            Direction::Up => pos.y -= op.length,
+        }
+        vertices.push(pos);
+    }
+    vertices
+}
+
+fn polygon_area(vertices: &[Point]) -> i64 {
+    let mut shoelace_area = 0;
+    let mut boundary = 0;
+    for i in 0..vertices.len() {
+        let current = vertices[i];
+        let next = vertices[(i + 1) % vertices.len()];
+        boundary += (next.x - current.x).abs() + (next.y - current.y).abs();
+        shoelace_area += current.x * next.y - next.x * current.y;
+    }
+    shoelace_area = shoelace_area.abs() / 2;
+
+    // Pick's theorem
+    let interior_area = shoelace_area - boundary / 2 + 1;
+
+    interior_area + boundary
+}
+
+fn split_line(line: &str) -> Result<[&str; 3], ParseError> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    parts
+        .try_into()
+        .map_err(|_| ParseError::new(line, line, "expected a direction, a length and a colour"))
+}
+
+fn part1_parse_line(line: &str) -> Result<Op, ParseError> {
+    let [direction_part, length_part, _] = split_line(line)?;
+    let direction = match direction_part {
+        "U" => Direction::Up,
+        "R" => Direction::Right,
+        "D" => Direction::Down,
+        "L" => Direction::Left,
+        _ => return Err(ParseError::new(line, direction_part, "invalid direction")),
+    };
+    let length: i64 = parse_number(line, length_part)?;
+    Ok(Op { direction, length })
+}
+
+fn part2_parse_line(line: &str) -> Result<Op, ParseError> {
+    let [_, _, colour] = split_line(line)?;
+    let code = colour
+        .strip_prefix("(#")
+        .and_then(|code| code.strip_suffix(')'))
+        .filter(|code| code.len() == 6 && code.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| ParseError::new(line, colour, "expected a colour like \"(#70c710)\""))?;
+    let direction = match &code[5..] {
+        "0" => Direction::Right,
+        "1" => Direction::Down,
+        "2" => Direction::Left,
+        "3" => Direction::Up,
+        c => return Err(ParseError::new(line, c, "invalid direction")),
+    };
+    let length = i64::from_str_radix(&code[..5], 16)
+        .map_err(|_| ParseError::new(line, &code[..5], "invalid length"))?;
+    Ok(Op { direction, length })
+}
+
+/**
+ * The dig plan read both ways: the plain instructions for part 1 and the
+ * instructions hidden in the colour codes for part 2.
+ */
+pub struct DigPlan {
+    ops: Vec<Op>,
+    decoded_ops: Vec<Op>,
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+
+    type Input<'a> = DigPlan;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<DigPlan, ParseError> {
+        Ok(DigPlan {
+            ops: parse_lines(input, part1_parse_line)?,
+            decoded_ops: parse_lines(input, part2_parse_line)?,
+        })
+    }
+
+    fn part1(plan: &DigPlan) -> i64 {
+        let polygon = polygon_create(&plan.ops);
+        polygon_area(&polygon)
+    }
+
+    fn part2(plan: &DigPlan) -> i64 {
+        let polygon = polygon_create(&plan.decoded_ops);
+        polygon_area(&polygon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day18::part1(&Day18::parse(SAMPLE).unwrap()), 62)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day18::part2(&Day18::parse(SAMPLE).unwrap()), 952408144115)
+    }
+}
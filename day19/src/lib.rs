@@ -0,0 +1,456 @@
+use common::parse_lines;
+use common::parse_number;
+use common::split_once;
+use common::ParseError;
+use common::Solution;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Cool,
+    Musical,
+    Aerodynamic,
+    Shiny,
+}
+
+impl TryFrom<char> for Category {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
+        match c {
+            'x' => Ok(Category::Cool),
+            'm' => Ok(Category::Musical),
+            'a' => Ok(Category::Aerodynamic),
+            's' => Ok(Category::Shiny),
+            _ => Err(c),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action<'a> {
+    Goto(&'a str),
+    Reject,
+    Accept,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule<'a> {
+    GreaterThan(Category, u64, Action<'a>),
+    LessThan(Category, u64, Action<'a>),
+    Otherwise(Action<'a>),
+}
+
+fn parse_label(label: &str) -> Action<'_> {
+    match label {
+        "A" => Action::Accept,
+        "R" => Action::Reject,
+        l => Action::Goto(l),
+    }
+}
+
+fn parse_rule(rule: &str) -> Result<Rule<'_>, ParseError> {
+    if let Some((condition, label)) = rule.split_once(':') {
+        let goto = parse_label(label);
+        let is_greater = condition.contains('>');
+        let (category_str, value_str) =
+            split_once(rule, condition, if is_greater { ">" } else { "<" })?;
+        let mut chars = category_str.chars();
+        let category = match (chars.next().map(Category::try_from), chars.next()) {
+            (Some(Ok(category)), None) => category,
+            _ => return Err(ParseError::new(rule, category_str, "invalid category")),
+        };
+        let value: u64 = parse_number(rule, value_str)?;
+        if is_greater {
+            Ok(Rule::GreaterThan(category, value, goto))
+        } else {
+            Ok(Rule::LessThan(category, value, goto))
+        }
+    } else {
+        Ok(Rule::Otherwise(parse_label(rule)))
+    }
+}
+
+fn parse_workflow(line: &str) -> Result<(&str, Vec<Rule<'_>>), ParseError> {
+    let (label, rules_str) = line
+        .strip_suffix('}')
+        .and_then(|line| line.split_once('{'))
+        .ok_or_else(|| ParseError::new(line, line, "expected \"<label>{<rules>}\""))?;
+    let rules: Vec<Rule> = rules_str
+        .split(',')
+        .map(|rule| parse_rule(rule).map_err(|err| err.within(line, rule)))
+        .collect::<Result<_, _>>()?;
+    Ok((label, rules))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rating {
+    cool: u64,
+    musical: u64,
+    aerodynamic: u64,
+    shiny: u64,
+}
+
+fn parse_rating(line: &str) -> Result<Rating, ParseError> {
+    let values: Vec<u64> = line
+        .trim_matches(|c| c == '{' || c == '}')
+        .split(',')
+        .map(|p| parse_number(line, split_once(line, p, "=")?.1))
+        .collect::<Result<_, _>>()?;
+    let [cool, musical, aerodynamic, shiny] = values[..] else {
+        return Err(ParseError::new(line, line, "expected 4 ratings"));
+    };
+    Ok(Rating {
+        cool,
+        musical,
+        aerodynamic,
+        shiny,
+    })
+}
+
+/**
+ * The workflows by label, and the ratings of the parts to sort.
+ */
+pub type System<'a> = (HashMap<&'a str, Vec<Rule<'a>>>, Vec<Rating>);
+
+fn rule_action<'a>(rule: &Rule<'a>) -> Action<'a> {
+    match *rule {
+        Rule::GreaterThan(_, _, action) => action,
+        Rule::LessThan(_, _, action) => action,
+        Rule::Otherwise(action) => action,
+    }
+}
+
+/**
+ * Check that sorting can always finish: there is an `in` workflow, every
+ * label sent to is defined, and every workflow ends in a fallback rule.
+ */
+fn check_workflows(
+    input: &str,
+    workflows_part: &str,
+    workflows: &[(&str, Vec<Rule>)],
+) -> Result<(), ParseError> {
+    let mut defined = HashSet::new();
+    for &(label, _) in workflows {
+        if !defined.insert(label) {
+            return Err(ParseError::new(input, label, "workflow defined twice"));
+        }
+    }
+    if !defined.contains("in") {
+        return Err(ParseError::new(
+            input,
+            workflows_part,
+            "expected an \"in\" workflow",
+        ));
+    }
+    for (label, rules) in workflows {
+        if !matches!(rules.last(), Some(Rule::Otherwise(_))) {
+            return Err(ParseError::new(
+                input,
+                label,
+                "workflow does not end in a fallback rule",
+            ));
+        }
+        for rule in rules {
+            if let Action::Goto(target) = rule_action(rule) {
+                if !defined.contains(target) {
+                    return Err(ParseError::new(input, target, "unknown workflow"));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse(input: &str) -> Result<System<'_>, ParseError> {
+    let (workflows_part, ratings_part) = split_once(input, input, "\n\n")?;
+    let workflows = parse_lines(workflows_part, parse_workflow)?;
+    let ratings: Vec<Rating> =
+        parse_lines(ratings_part, parse_rating).map_err(|err| err.within(input, ratings_part))?;
+    check_workflows(input, workflows_part, &workflows)?;
+    let workflows: HashMap<&str, Vec<Rule>> = workflows.into_iter().collect();
+    Ok((workflows, ratings))
+}
+
+fn rating_category_value(rating: &Rating, category: &Category) -> u64 {
+    match category {
+        Category::Cool => rating.cool,
+        Category::Musical => rating.musical,
+        Category::Aerodynamic => rating.aerodynamic,
+        Category::Shiny => rating.shiny,
+    }
+}
+
+fn rule_matches<'a>(rule: &'a Rule<'a>, rating: &'a Rating) -> Option<Action<'a>> {
+    match rule {
+        Rule::GreaterThan(category, value, action) => {
+            let rating_value = rating_category_value(rating, category);
+            if rating_value > *value {
+                Some(*action)
+            } else {
+                None
+            }
+        }
+        Rule::LessThan(category, value, action) => {
+            let rating_value = rating_category_value(rating, category);
+            if rating_value < *value {
+                Some(*action)
+            } else {
+                None
+            }
+        }
+        Rule::Otherwise(action) => Some(*action),
+    }
+}
+
+fn get_action<'a>(rules: &'a [Rule<'a>], rating: &'a Rating) -> Action<'a> {
+    rules
+        .iter()
+        .find_map(|rule| rule_matches(rule, rating))
+        .expect("parse checks that workflows end in a fallback rule")
+}
+
+fn apply_bound(bound: Rating, category: &Category, value: u64) -> Rating {
+    let mut new_bound = bound;
+    match category {
+        Category::Cool => new_bound.cool = value,
+        Category::Musical => new_bound.musical = value,
+        Category::Aerodynamic => new_bound.aerodynamic = value,
+        Category::Shiny => new_bound.shiny = value,
+    }
+    new_bound
+}
+
+fn rule_apply(rule: &Rule, lower_bound: Rating, upper_bound: Rating) -> (Rating, Rating) {
+    match rule {
+        Rule::GreaterThan(category, value, _) => {
+            let new_lower_bound = apply_bound(lower_bound, category, value.saturating_add(1));
+            (new_lower_bound, upper_bound)
+        }
+        Rule::LessThan(category, value, _) => {
+            let new_upper_bound = apply_bound(upper_bound, category, value.saturating_sub(1));
+            (lower_bound, new_upper_bound)
+        }
+        Rule::Otherwise(_) => (lower_bound, upper_bound),
+    }
+}
+
+fn rule_invert(rule: &Rule, lower_bound: Rating, upper_bound: Rating) -> (Rating, Rating) {
+    match rule {
+        Rule::LessThan(category, value, _) => {
+            let new_lower_bound = apply_bound(lower_bound, category, *value);
+            (new_lower_bound, upper_bound)
+        }
+        Rule::GreaterThan(category, value, _) => {
+            let new_upper_bound = apply_bound(upper_bound, category, *value);
+            (lower_bound, new_upper_bound)
+        }
+        Rule::Otherwise(_) => (lower_bound, upper_bound),
+    }
+}
+
+fn rule_process(
+    rule: &Rule,
+    workflow_map: &HashMap<&str, Vec<Rule>>,
+    lower_bound: Rating,
+    upper_bound: Rating,
+) -> u64 {
+    let (lower_bound, upper_bound) = rule_apply(rule, lower_bound, upper_bound);
+    match rule_action(rule) {
+        Action::Accept => combinations(lower_bound, upper_bound),
+        Action::Reject => 0,
+        Action::Goto(label) => workflow_run(label, workflow_map, lower_bound, upper_bound),
+    }
+}
+
+fn workflow_run(
+    label: &str,
+    workflow_map: &HashMap<&str, Vec<Rule>>,
+    lower_bound: Rating,
+    upper_bound: Rating,
+) -> u64 {
+    let rules = &workflow_map[label];
+    let mut total = 0;
+    let mut lower_bound = lower_bound;
+    let mut upper_bound = upper_bound;
+    for rule in rules {
+        total += rule_process(rule, workflow_map, lower_bound, upper_bound);
+        (lower_bound, upper_bound) = rule_invert(rule, lower_bound, upper_bound);
+    }
+    total
+}
+
+/**
+ * Ratings between the bounds, inclusive. Bounds that cross leave none.
+ */
+fn combinations(lower_bound: Rating, upper_bound: Rating) -> u64 {
+    let count = |lower: u64, upper: u64| (upper + 1).saturating_sub(lower);
+    let cool = count(lower_bound.cool, upper_bound.cool);
+    let musical = count(lower_bound.musical, upper_bound.musical);
+    let aerodynamic = count(lower_bound.aerodynamic, upper_bound.aerodynamic);
+    let shiny = count(lower_bound.shiny, upper_bound.shiny);
+    cool * musical * aerodynamic * shiny
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+
+    type Input<'a> = System<'a>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<System<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(system: &System<'_>) -> u64 {
+        let (workflow_map, ratings) = system;
+        let mut total: u64 = 0;
+        for rating in ratings {
+            let mut current = "in";
+            loop {
+                let action = get_action(&workflow_map[current], rating);
+                match action {
+                    Action::Accept => {
+                        total += rating.cool + rating.musical + rating.aerodynamic + rating.shiny;
+                        break;
+                    }
+                    Action::Reject => break,
+                    Action::Goto(label) => current = label,
+                }
+            }
+        }
+        total
+    }
+
+    fn part2(system: &System<'_>) -> u64 {
+        let (workflow_map, _) = system;
+        let lower_bound = Rating {
+            cool: 1,
+            musical: 1,
+            aerodynamic: 1,
+            shiny: 1,
+        };
+        let upper_bound = Rating {
+            cool: 4000,
+            musical: 4000,
+            aerodynamic: 4000,
+            shiny: 4000,
+        };
+        workflow_run("in", workflow_map, lower_bound, upper_bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!(
+            parse_rule("a<2006:qkq"),
+            Ok(Rule::LessThan(
+                Category::Aerodynamic,
+                2006,
+                Action::Goto("qkq")
+            ))
+        );
+        assert_eq!(
+            parse_rule("m>2090:A"),
+            Ok(Rule::GreaterThan(Category::Musical, 2090, Action::Accept))
+        );
+        assert_eq!(parse_rule("rfg"), Ok(Rule::Otherwise(Action::Goto("rfg"))));
+    }
+
+    #[test]
+    fn test_parse_rating() {
+        assert_eq!(
+            parse_rating("{x=787,m=2655,a=1222,s=2876}"),
+            Ok(Rating {
+                cool: 787,
+                musical: 2655,
+                aerodynamic: 1222,
+                shiny: 2876
+            })
+        )
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("px{a<2006:qkq,rfg}\nrfg{q<537:gd,A}\n\n{x=1,m=2,a=3,s=4}").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "q"));
+        assert_eq!(err.message, "invalid category");
+        let err = parse("px{a<2006:qkq,rfg}\n\n{x=1,m=2,a=3,s=4}\n{x=1,m=2,a=3}").unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (4, "expected 4 ratings"));
+    }
+
+    #[test]
+    fn test_workflow_errors() {
+        let err = |input| parse(input).unwrap_err();
+        let no_fallback = err("in{x>10:qq,A}\nqq{x>10:A}\n\n{x=1,m=2,a=3,s=4}");
+        assert_eq!(
+            (
+                no_fallback.line,
+                no_fallback.column,
+                no_fallback.text.as_str()
+            ),
+            (2, 1, "qq")
+        );
+        assert_eq!(
+            no_fallback.message,
+            "workflow does not end in a fallback rule"
+        );
+        let unknown = err("in{x>10:qq,A}\n\n{x=1,m=2,a=3,s=4}");
+        assert_eq!(
+            (unknown.line, unknown.column, unknown.message.as_str()),
+            (1, 9, "unknown workflow")
+        );
+        let no_in = err("px{A}\n\n{x=1,m=2,a=3,s=4}");
+        assert_eq!(no_in.message, "expected an \"in\" workflow");
+        let twice = err("in{A}\nin{R}\n\n{x=1,m=2,a=3,s=4}");
+        assert_eq!(
+            (twice.line, twice.message.as_str()),
+            (2, "workflow defined twice")
+        );
+    }
+
+    #[test]
+    fn test_empty_bounds() {
+        let system = parse("in{x>5000:A,m<1:A,R}\n\n{x=1,m=2,a=3,s=4}").unwrap();
+        assert_eq!((Day19::part1(&system), Day19::part2(&system)), (0, 0));
+    }
+
+    static SAMPLE: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day19::part1(&Day19::parse(SAMPLE).unwrap()), 19114)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day19::part2(&Day19::parse(SAMPLE).unwrap()),
+            167409079868000
+        )
+    }
+}
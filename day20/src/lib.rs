@@ -0,0 +1,309 @@
+use common::math;
+use common::parse_lines;
+use common::split_once;
+use common::MaybeAnswer;
+use common::ParseError;
+use common::Solution;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone)]
+pub enum Module<'a> {
+    Broadcaster {
+        outputs: Vec<&'a str>,
+    },
+    FlipFlop {
+        state: bool,
+        outputs: Vec<&'a str>,
+    },
+    Conjuction {
+        states: HashMap<&'a str, bool>,
+        outputs: Vec<&'a str>,
+    },
+}
+
+fn parse_line(line: &str) -> Result<(&str, Module<'_>), ParseError> {
+    let (name, outputs_str) = split_once(line, line, " -> ")?;
+    let outputs: Vec<&str> = outputs_str.split(", ").collect();
+    if name == "broadcaster" {
+        Ok(("broadcaster", Module::Broadcaster { outputs }))
+    } else if let Some(label) = name.strip_prefix('%') {
+        Ok((
+            label,
+            Module::FlipFlop {
+                state: false,
+                outputs,
+            },
+        ))
+    } else if let Some(label) = name.strip_prefix('&') {
+        Ok((
+            label,
+            Module::Conjuction {
+                states: HashMap::new(),
+                outputs,
+            },
+        ))
+    } else {
+        Err(ParseError::new(line, name, "invalid module"))
+    }
+}
+
+fn parse(input: &str) -> Result<HashMap<&str, Module<'_>>, ParseError> {
+    let mut modules: HashMap<&str, Module<'_>> = HashMap::new();
+    let mut conjuctions: HashSet<&str> = HashSet::new();
+    for (label, module) in parse_lines(input, parse_line)? {
+        if let Module::Conjuction {
+            states: _,
+            outputs: _,
+        } = module
+        {
+            conjuctions.insert(label);
+        }
+        modules.insert(label, module);
+    }
+    // Initialize conjuctions.
+    let mut conjunction_links: Vec<(&str, &str)> = Vec::new();
+    for (from, module) in modules.iter() {
+        let outputs = match module {
+            Module::Conjuction { states: _, outputs } => outputs,
+            Module::FlipFlop { state: _, outputs } => outputs,
+            Module::Broadcaster { outputs } => outputs,
+        };
+        for to in outputs {
+            if conjuctions.contains(to) {
+                conjunction_links.push((from, to));
+            }
+        }
+    }
+    for (from, to) in conjunction_links {
+        if let Some(Module::Conjuction { states, outputs: _ }) = modules.get_mut(to) {
+            states.insert(from, false);
+        }
+    }
+    if !matches!(modules.get("broadcaster"), Some(Module::Broadcaster { .. })) {
+        return Err(ParseError::new(
+            input,
+            &input[input.len()..],
+            "missing broadcaster",
+        ));
+    }
+    Ok(modules)
+}
+
+/**
+ * Records the presses at which each input of the conjunction feeding rx sends
+ * it a high pulse.
+ */
+#[derive(Debug)]
+struct Monitor<'a> {
+    presses: usize,
+    rx_conjunctor: &'a str,
+    hits: HashMap<&'a str, Vec<usize>>,
+}
+
+fn press_button<'a>(
+    modules: &mut HashMap<&'a str, Module<'a>>,
+    mut part2: Option<&mut Monitor<'a>>,
+) -> (usize, usize) {
+    let mut low_pulses = 1; // Button sends low pulse to broadcaster.
+    let mut high_pulses = 0;
+    let mut signals: VecDeque<(&str, &str, bool)> = VecDeque::new();
+    if let Module::Broadcaster {
+        outputs: initial_outputs,
+    } = modules.get("broadcaster").unwrap()
+    {
+        let from = "broadcaster";
+        for to in initial_outputs {
+            signals.push_back((from, *to, false));
+        }
+    } else {
+        unreachable!()
+    }
+    while !signals.is_empty() {
+        let (from, to, pulse) = signals.pop_front().unwrap();
+        if pulse {
+            high_pulses += 1;
+        } else {
+            low_pulses += 1;
+        }
+        if !modules.contains_key(to) {
+            continue;
+        }
+        let dst_module = modules.get_mut(to).unwrap();
+        match dst_module {
+            Module::FlipFlop { state, outputs } => {
+                if !pulse {
+                    *state = !*state;
+                    for label in outputs {
+                        signals.push_back((to, label, *state));
+                    }
+                }
+            }
+            Module::Conjuction { states, outputs } => {
+                if let Some(monitor) = part2.as_deref_mut() {
+                    if pulse && to == monitor.rx_conjunctor {
+                        let hits = monitor.hits.entry(from).or_default();
+                        if hits.last() != Some(&monitor.presses) {
+                            hits.push(monitor.presses);
+                        }
+                    }
+                }
+                states.insert(from, pulse);
+                let conjuction_pulse = !states.values().all(|s| *s);
+                for label in outputs {
+                    signals.push_back((to, label, conjuction_pulse));
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    (low_pulses, high_pulses)
+}
+
+fn find_rx_conjunctor<'a>(modules: &HashMap<&'a str, Module>) -> Option<&'a str> {
+    for (from, module) in modules.iter() {
+        let outputs = match module {
+            Module::Broadcaster { outputs } => outputs,
+            Module::FlipFlop { state: _, outputs } => outputs,
+            Module::Conjuction { states: _, outputs } => outputs,
+        };
+        for to in outputs {
+            if *to == "rx" {
+                return Some(from);
+            }
+        }
+    }
+    None
+}
+
+/**
+ * Presses to give up after if an input of the conjunction feeding rx has not
+ * fired twice, as then it likely never will.
+ */
+const MAX_PRESSES: usize = 1 << 20;
+
+/**
+ * Fewest presses that send rx a low pulse, assuming rx is fed by a single
+ * conjunction whose inputs each fire on a fixed cycle. None if the network
+ * is not shaped like that or the inputs never fire together.
+ */
+fn presses_until_rx(network: &HashMap<&str, Module<'_>>) -> Option<usize> {
+    let mut modules = network.clone();
+    let rx_conjunctor = find_rx_conjunctor(&modules)?;
+    let Some(Module::Conjuction { states, outputs: _ }) = modules.get(rx_conjunctor) else {
+        return None;
+    };
+    let rx_input_len = states.len();
+    if rx_input_len == 0 {
+        return None;
+    }
+    let mut monitor = Monitor {
+        presses: 0,
+        rx_conjunctor,
+        hits: HashMap::new(),
+    };
+    // Watch each input fire twice to learn its offset and period.
+    loop {
+        if monitor.presses == MAX_PRESSES {
+            return None;
+        }
+        monitor.presses += 1;
+        press_button(&mut modules, Some(&mut monitor));
+        let watched = monitor.hits.values().filter(|hits| hits.len() >= 2).count();
+        if watched >= rx_input_len {
+            break;
+        }
+    }
+    let (residue, modulus) = math::crt(
+        monitor
+            .hits
+            .values()
+            .map(|hits| (hits[0] as i128, (hits[1] - hits[0]) as i128)),
+    )?;
+    let earliest = monitor.hits.values().map(|hits| hits[0]).max().unwrap_or(0);
+    Some(math::next_congruent(residue, modulus, earliest as i128) as usize)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+
+    type Input<'a> = HashMap<&'a str, Module<'a>>;
+    type Part1 = usize;
+    type Part2 = MaybeAnswer<usize>;
+
+    fn parse(input: &str) -> Result<HashMap<&str, Module<'_>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(network: &HashMap<&str, Module<'_>>) -> usize {
+        let mut modules = network.clone();
+        let mut low_pulses = 0;
+        let mut high_pulses = 0;
+        for _ in 0..1000 {
+            let (lo, hi) = press_button(&mut modules, None);
+            low_pulses += lo;
+            high_pulses += hi;
+        }
+        low_pulses * high_pulses
+    }
+
+    fn part2(network: &HashMap<&str, Module<'_>>) -> MaybeAnswer<usize> {
+        MaybeAnswer(presses_until_rx(network))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day20::part1(
+                &Day20::parse(
+                    "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a"
+                )
+                .unwrap()
+            ),
+            32000000
+        );
+        assert_eq!(
+            Day20::part1(
+                &Day20::parse(
+                    "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output"
+                )
+                .unwrap()
+            ),
+            11687500
+        )
+    }
+
+    #[test]
+    fn test_part2() {
+        let network = Day20::parse("broadcaster -> a\n%a -> con\n&con -> rx").unwrap();
+        assert_eq!(Day20::part2(&network), MaybeAnswer(Some(1)));
+        // Nothing feeds rx.
+        let network = Day20::parse("broadcaster -> a\n%a -> con\n&con -> output").unwrap();
+        assert_eq!(Day20::part2(&network), MaybeAnswer(None));
+        // rx is fed by a flip-flop.
+        let network = Day20::parse("broadcaster -> a\n%a -> rx").unwrap();
+        assert_eq!(Day20::part2(&network), MaybeAnswer(None));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day20::parse("%a -> b\n%b -> a").unwrap_err();
+        assert_eq!(err.message, "missing broadcaster");
+    }
+}
@@ -0,0 +1,141 @@
+use common::ParseError;
+use common::Solution;
+use grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Garden,
+    Rock,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
+        match c {
+            '.' => Ok(Tile::Garden),
+            '#' => Ok(Tile::Rock),
+            'S' => Ok(Tile::Garden),
+            _ => Err(c),
+        }
+    }
+}
+
+/**
+ * The map of garden plots and rocks, and the starting position as (row, col).
+ */
+pub type Map = (Grid<Tile>, (isize, isize));
+
+fn parse(input: &str) -> Result<Map, ParseError> {
+    let map = Grid::parse_with(input, |c| Tile::try_from(c).ok(), "invalid tile")?;
+    let start_pos = input
+        .lines()
+        .enumerate()
+        .find_map(|(row, line)| {
+            let col = line.chars().position(|c| c == 'S')?;
+            Some((row as isize, col as isize))
+        })
+        .ok_or_else(|| ParseError::new(input, &input[input.len()..], "missing start tile 'S'"))?;
+    Ok((map, start_pos))
+}
+
+/**
+ * Tests if (row, col) is a garden with infinite wrapping of map.
+ */
+fn is_garden(map: &Grid<Tile>, row: isize, col: isize) -> bool {
+    map.get_wrapping(col, row) == Some(&Tile::Garden)
+}
+
+/**
+ * Count the plots reachable in exactly each number of `max_steps`. Stepping back
+ * and forth means a plot at distance `d` is reachable in any number of steps
+ * at least `d` with the same parity.
+ */
+fn bfs_steps(map: &Grid<Tile>, start: (isize, isize), max_steps: [usize; 3]) -> [usize; 3] {
+    let limit = max_steps.into_iter().max().unwrap_or(0);
+    let distances: Vec<usize> = graph::bfs(start, |&(row, col)| {
+        [(-1, 0), (1, 0), (0, 1), (0, -1)]
+            .into_iter()
+            .map(move |(dr, dc)| (row + dr, col + dc))
+            .filter(|&(r, c)| is_garden(map, r, c))
+    })
+    .map(|(_, distance)| distance)
+    .take_while(|&distance| distance <= limit)
+    .collect();
+    max_steps.map(|steps| {
+        distances
+            .iter()
+            .filter(|&&distance| distance <= steps && distance % 2 == steps % 2)
+            .count()
+    })
+}
+
+fn reachable(map: &Grid<Tile>, start: (isize, isize), steps: usize) -> usize {
+    let max_steps = [0, 0, steps];
+    bfs_steps(map, start, max_steps)[2]
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+
+    type Input<'a> = Map;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Map, ParseError> {
+        parse(input)
+    }
+
+    fn part1(garden: &Map) -> usize {
+        let (map, start) = garden;
+        reachable(map, *start, 64)
+    }
+
+    fn part2(garden: &Map) -> usize {
+        let (map, start) = garden;
+        // Assumes there are no rocks in the same row/column and
+        // the edges are empty. Therefore as we expand out the
+        // number of steps is quadratic.
+        let progression = bfs_steps(map, *start, [65, 65 + 131, 65 + 131 * 2]);
+        let x = (26501365 - 65) / 131;
+        let (a, b, c) = quadratic_fit(progression);
+        a * x * x + b * x + c
+    }
+}
+
+fn quadratic_fit(progression: [usize; 3]) -> (usize, usize, usize) {
+    let [y1, y2, y3] = progression;
+    let a = (y3 - 2 * y2 + y1) / 2;
+    let b = (y2 - y1) - a;
+    let c = y1;
+
+    (a, b, c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn test_part1() {
+        let (map, start) = parse(SAMPLE).unwrap();
+        assert_eq!(reachable(&map, start, 6), 16);
+        assert_eq!(reachable(&map, start, 10), 50);
+        assert_eq!(reachable(&map, start, 50), 1594);
+        assert_eq!(reachable(&map, start, 100), 6536)
+    }
+}
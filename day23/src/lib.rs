@@ -0,0 +1,182 @@
+use common::ParseError;
+use common::Solution;
+use grid::{Coord, Direction, Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Path,
+    Forest,
+    Slope(Direction),
+}
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
+        match c {
+            '.' => Ok(Self::Path),
+            '#' => Ok(Self::Forest),
+            '>' => Ok(Self::Slope(Direction::Right)),
+            'v' => Ok(Self::Slope(Direction::Down)),
+            '<' => Ok(Self::Slope(Direction::Left)),
+            '^' => Ok(Self::Slope(Direction::Up)),
+            _ => Err(c),
+        }
+    }
+}
+
+/**
+ * Where the hike starts: the path tile in the top row.
+ */
+const START: Coord = Coord { x: 1, y: 0 };
+
+fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+    let grid = Grid::parse_with(input, |c| Tile::try_from(c).ok(), "invalid tile")?;
+    if grid.get(START).is_none_or(|&tile| tile == Tile::Forest) {
+        let first_line = input.lines().next().unwrap_or(input);
+        return Err(ParseError::new(input, first_line, "no start"));
+    }
+    Ok(grid)
+}
+
+/**
+ * Positions reachable in one step from `pos`. Slopes can only be walked down
+ * unless `part2` lets them be climbed.
+ */
+fn moves(grid: &Grid<Tile>, pos: Coord, part2: bool) -> Vec<Coord> {
+    let directions = match &grid[pos] {
+        _ if part2 => Direction::ALL.as_slice(),
+        Tile::Path => Direction::ALL.as_slice(),
+        Tile::Slope(direction) => std::slice::from_ref(direction),
+        Tile::Forest => unreachable!(),
+    };
+    directions
+        .iter()
+        .filter_map(|&direction| grid.step(pos, direction))
+        .filter(|&next| grid[next] != Tile::Forest)
+        .collect()
+}
+
+fn is_junction(grid: &Grid<Tile>, pos: Coord, start: Coord) -> bool {
+    pos == start
+        || pos.y == grid.height() - 1
+        || grid
+            .neighbours4(pos)
+            .filter(|&next| grid[next] != Tile::Forest)
+            .count()
+            > 2
+}
+
+/**
+ * Longest hike from `start` to the bottom row. The trails are long corridors
+ * between a few junctions, so they are first reduced to a graph of junctions
+ * weighted by corridor length.
+ */
+fn longest_hike(grid: &Grid<Tile>, start: Coord, part2: bool) -> usize {
+    let junctions: Vec<Coord> = grid
+        .coords()
+        .filter(|&pos| grid[pos] != Tile::Forest && is_junction(grid, pos, start))
+        .collect();
+    let index = |pos: Coord| junctions.iter().position(|&junction| junction == pos);
+    let mut edges: Vec<Vec<(usize, usize)>> = vec![Vec::new(); junctions.len()];
+    for (from, &junction) in junctions.iter().enumerate() {
+        for first in moves(grid, junction, part2) {
+            let (mut previous, mut current, mut dist) = (junction, first, 1);
+            // Follow the corridor until the next junction or a dead end.
+            let end = loop {
+                if let Some(to) = index(current) {
+                    break Some(to);
+                }
+                let Some(next) = moves(grid, current, part2)
+                    .into_iter()
+                    .find(|&next| next != previous)
+                else {
+                    break None;
+                };
+                (previous, current, dist) = (current, next, dist + 1);
+            };
+            if let Some(to) = end {
+                edges[from].push((to, dist));
+            }
+        }
+    }
+    let start = index(start).expect("the start is a junction");
+    graph::longest_path(
+        start,
+        |&node| edges[node].iter().copied(),
+        |&node| junctions[node].y == grid.height() - 1,
+    )
+    .unwrap_or(0)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+
+    type Input<'a> = Grid<Tile>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<Tile>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(grid: &Grid<Tile>) -> usize {
+        longest_hike(grid, START, false)
+    }
+
+    fn part2(grid: &Grid<Tile>) -> usize {
+        longest_hike(grid, START, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day23::part1(&Day23::parse(SAMPLE).unwrap()), 94)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day23::part2(&Day23::parse(SAMPLE).unwrap()), 154)
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Day23::parse("").unwrap_err().message, "no start");
+        let err = Day23::parse("###\n#.#").unwrap_err();
+        assert_eq!(
+            (err.line, err.text.as_str(), err.message.as_str()),
+            (1, "###", "no start")
+        );
+        assert_eq!(Day23::part1(&Day23::parse("#.#\n#.#").unwrap()), 1);
+    }
+}
@@ -0,0 +1,168 @@
+use common::parse_lines;
+use common::parse_number;
+use common::split_once;
+use common::ParseError;
+use common::Solution;
+use z3::ast::Ast;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    position: (i64, i64, i64),
+    velocity: (i64, i64, i64),
+}
+
+fn parse_vector(line: &str, text: &str) -> Result<(i64, i64, i64), ParseError> {
+    let numbers: Vec<i64> = text
+        .split(',')
+        .map(|s| parse_number(line, s.trim()))
+        .collect::<Result<_, _>>()?;
+    let [x, y, z] = numbers[..] else {
+        return Err(ParseError::new(line, text.trim(), "expected 3 numbers"));
+    };
+    Ok((x, y, z))
+}
+
+fn parse_line(line: &str) -> Result<Hailstone, ParseError> {
+    let (position, velocity) = split_once(line, line, "@")?;
+    Ok(Hailstone {
+        position: parse_vector(line, position)?,
+        velocity: parse_vector(line, velocity)?,
+    })
+}
+
+fn parse(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+    parse_lines(input, parse_line)
+}
+
+/**
+ * Count the pairs of hailstones whose paths cross within the test area
+ * bounded by `min` and `max` on both axes, ignoring the Z axis.
+ */
+fn count_intersections(hailstones: &[Hailstone], min: i64, max: i64) -> usize {
+    // https://stackoverflow.com/questions/563198/how-do-you-detect-where-two-line-segments-intersect
+    fn cross(v: (i64, i64), w: (i64, i64)) -> i64 {
+        v.0 * w.1 - v.1 * w.0
+    }
+    fn intersection(a: Hailstone, b: Hailstone) -> Option<(i64, i64)> {
+        let p = (a.position.0, a.position.1);
+        let r = (a.velocity.0, a.velocity.1);
+        let q = (b.position.0, b.position.1);
+        let s = (b.velocity.0, b.velocity.1);
+        let rxs = cross(r, s);
+        if rxs == 0 {
+            return None;
+        }
+        let q_minus_p = (q.0 - p.0, q.1 - p.1);
+        let t = cross(q_minus_p, s) as f64 / rxs as f64;
+        let u = cross(q_minus_p, r) as f64 / rxs as f64;
+        if t < 0_f64 || u < 0_f64 {
+            return None;
+        }
+        Some((
+            (p.0 as f64 + (t * r.0 as f64)) as i64,
+            (p.1 as f64 + (t * r.1 as f64)) as i64,
+        ))
+    }
+    let mut count = 0;
+    for i in 0..hailstones.len() {
+        for j in i + 1..hailstones.len() {
+            let a = hailstones[i];
+            let b = hailstones[j];
+            if let Some((x, y)) = intersection(a, b) {
+                if min <= x && x <= max && min <= y && y <= max {
+                    count += 1
+                }
+            }
+        }
+    }
+    count
+}
+
+fn throw_rock(hailstones: &[Hailstone]) -> i64 {
+    let cfg = z3::Config::new();
+    let context = z3::Context::new(&cfg);
+    let solver = z3::Solver::new(&context);
+
+    let x = z3::ast::Int::new_const(&context, "x");
+    let y = z3::ast::Int::new_const(&context, "y");
+    let z = z3::ast::Int::new_const(&context, "z");
+    let vx = z3::ast::Int::new_const(&context, "vx");
+    let vy = z3::ast::Int::new_const(&context, "vy");
+    let vz = z3::ast::Int::new_const(&context, "vz");
+
+    for (i, hs) in hailstones.iter().take(3).enumerate() {
+        let a = z3::ast::Int::from_i64(&context, hs.position.0);
+        let va = z3::ast::Int::from_i64(&context, hs.velocity.0);
+        let b = z3::ast::Int::from_i64(&context, hs.position.1);
+        let vb = z3::ast::Int::from_i64(&context, hs.velocity.1);
+        let c = z3::ast::Int::from_i64(&context, hs.position.2);
+        let vc = z3::ast::Int::from_i64(&context, hs.velocity.2);
+
+        let t = z3::ast::Int::new_const(&context, format!("t{i}"));
+        solver.assert(&t.gt(&z3::ast::Int::from_i64(&context, 0)));
+        solver.assert(&(x.clone() + vx.clone() * t.clone())._eq(&(a + va * t.clone())));
+        solver.assert(&(y.clone() + vy.clone() * t.clone())._eq(&(b + vb * t.clone())));
+        solver.assert(&(z.clone() + vz.clone() * t.clone())._eq(&(c + vc * t.clone())));
+    }
+    if solver.check() == z3::SatResult::Sat {
+        let m = solver.get_model().unwrap();
+        return m.eval(&(x + y + z), true).unwrap().as_i64().unwrap();
+    }
+    panic!("Failed to solve!")
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+
+    type Input<'a> = Vec<Hailstone>;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(hailstones: &Vec<Hailstone>) -> usize {
+        count_intersections(hailstones, 200_000_000_000_000, 400_000_000_000_000)
+    }
+
+    fn part2(hailstones: &Vec<Hailstone>) -> i64 {
+        throw_rock(hailstones)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("19, 13, 30 @ -2,  1, -2"),
+            Ok(Hailstone {
+                position: (19, 13, 30),
+                velocity: (-2, 1, -2)
+            })
+        );
+        let err = parse_line("19, 13 @ -2,  1, -2").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (1, "19, 13"));
+    }
+
+    static SAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(count_intersections(&parse(SAMPLE).unwrap(), 7, 27), 2)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day24::part2(&Day24::parse(SAMPLE).unwrap()), 47)
+    }
+}
@@ -0,0 +1,103 @@
+use common::parse_lines;
+use common::split_once;
+use common::MaybeAnswer;
+use common::NoAnswer;
+use common::ParseError;
+use common::Solution;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+fn parse_line(line: &str) -> Result<(&str, Vec<&str>), ParseError> {
+    let (key, connected_to) = split_once(line, line, ": ")?;
+    Ok((key, connected_to.split_whitespace().collect()))
+}
+
+fn parse(input: &str) -> Result<HashMap<&str, HashSet<&str>>, ParseError> {
+    let mut graph: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for (key, connected_to) in parse_lines(input, parse_line)? {
+        for node in connected_to {
+            // Connect in both directions.
+            graph.entry(key).or_default().insert(node);
+            graph.entry(node).or_default().insert(key);
+        }
+    }
+    if graph.is_empty() {
+        return Err(ParseError::new(input, input, "empty graph"));
+    }
+    Ok(graph)
+}
+
+/**
+ * Size of one of the two groups left after disconnecting three wires, or
+ * None if no three wires split the graph. The first component is on one
+ * side; the minimum cut between it and any component on the other side has
+ * exactly three wires.
+ */
+fn group_size(graph: &HashMap<&str, HashSet<&str>>) -> Option<usize> {
+    let mut components = graph.keys().copied();
+    let source = components.next()?;
+    components
+        .map(|sink| graph::min_cut(source, sink, |node: &&str| graph[node].iter().copied()))
+        .find(|cut| cut.size == 3)
+        .map(|cut| cut.side.len())
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+
+    type Input<'a> = HashMap<&'a str, HashSet<&'a str>>;
+    type Part1 = MaybeAnswer<usize>;
+    type Part2 = NoAnswer;
+
+    fn parse(input: &str) -> Result<HashMap<&str, HashSet<&str>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(graph: &HashMap<&str, HashSet<&str>>) -> MaybeAnswer<usize> {
+        MaybeAnswer(group_size(graph).map(|size| size * (graph.len() - size)))
+    }
+
+    fn part2(_: &HashMap<&str, HashSet<&str>>) -> NoAnswer {
+        NoAnswer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day25::part1(&Day25::parse(SAMPLE).unwrap()),
+            MaybeAnswer(Some(54))
+        );
+        // One wire joins the two halves, so no three wires split the graph.
+        assert_eq!(
+            Day25::part1(&Day25::parse("a: b").unwrap()),
+            MaybeAnswer(None)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Day25::parse("").unwrap_err().message, "empty graph");
+        assert_eq!(Day25::parse("a b").unwrap_err().column, 1);
+    }
+}
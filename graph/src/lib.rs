@@ -0,0 +1,410 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::ops::Add;
+
+/**
+ * A cheapest path found by `dijkstra` or `astar`, from the start node to the
+ * goal inclusive.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    pub nodes: Vec<N>,
+}
+
+/**
+ * Cheapest path from `start` to the first node satisfying `is_goal`.
+ * `neighbours` yields each reachable node with the cost of the edge to it.
+ */
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/**
+ * Cheapest path like `dijkstra`, guided by `heuristic`, which must never
+ * overestimate the remaining cost to a goal.
+ */
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // Nodes are stored once and referred to by index, so N needs no ordering.
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start, 0)]);
+    let mut best: Vec<(C, Option<usize>)> = vec![(C::default(), None)];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&nodes[0]), C::default(), 0))]);
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        if cost > best[index].0 {
+            continue;
+        }
+        if is_goal(&nodes[index]) {
+            let mut path = vec![nodes[index].clone()];
+            let mut current = index;
+            while let Some(previous) = best[current].1 {
+                path.push(nodes[previous].clone());
+                current = previous;
+            }
+            path.reverse();
+            return Some(Path { cost, nodes: path });
+        }
+        for (next, step) in neighbours(&nodes[index]) {
+            let next_cost = cost + step;
+            let next_index = match indices.get(&next) {
+                Some(&i) if best[i].0 <= next_cost => continue,
+                Some(&i) => {
+                    best[i] = (next_cost, Some(index));
+                    i
+                }
+                None => {
+                    let i = nodes.len();
+                    nodes.push(next.clone());
+                    indices.insert(next, i);
+                    best.push((next_cost, Some(index)));
+                    i
+                }
+            };
+            let estimate = next_cost + heuristic(&nodes[next_index]);
+            queue.push(Reverse((estimate, next_cost, next_index)));
+        }
+    }
+    None
+}
+
+/**
+ * Breadth-first traversal yielding each reachable node once with its distance
+ * from the start, nearest first. Neighbours are only requested for nodes that
+ * have been yielded, so the graph may be infinite.
+ */
+pub struct Bfs<N, F> {
+    queue: VecDeque<(N, usize)>,
+    seen: HashSet<N>,
+    neighbours: F,
+}
+
+impl<N, F, I> Iterator for Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<(N, usize)> {
+        let (node, distance) = self.queue.pop_front()?;
+        for next in (self.neighbours)(&node) {
+            if self.seen.insert(next.clone()) {
+                self.queue.push_back((next, distance + 1));
+            }
+        }
+        Some((node, distance))
+    }
+}
+
+pub fn bfs<N, F, I>(start: N, neighbours: F) -> Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Bfs {
+        queue: VecDeque::from([(start.clone(), 0)]),
+        seen: HashSet::from([start]),
+        neighbours,
+    }
+}
+
+/**
+ * Distance from `start` to every node reachable from it.
+ */
+pub fn bfs_distances<N, I>(start: N, neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs(start, neighbours).collect()
+}
+
+/**
+ * Shortest path by number of edges from `start` to the first node satisfying
+ * `is_goal`, both inclusive.
+ */
+pub fn bfs_path<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            let mut path = vec![node];
+            while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+                path.push(parent.clone());
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in neighbours(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/**
+ * Label every node with the index of its connected component. Components are
+ * numbered from 0 in the order their first node appears in `nodes`.
+ * `neighbours` must be symmetric.
+ */
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut labels = HashMap::new();
+    let mut components = 0;
+    for node in nodes {
+        if labels.contains_key(&node) {
+            continue;
+        }
+        for (member, _) in bfs(node, &mut neighbours) {
+            labels.insert(member, components);
+        }
+        components += 1;
+    }
+    labels
+}
+
+/**
+ * A minimum edge cut between two nodes: the number of edges cut, and the nodes
+ * left on the source's side.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut<N: Eq + Hash> {
+    pub size: usize,
+    pub side: HashSet<N>,
+}
+
+/**
+ * Minimum edge cut separating `source` from `sink` in an undirected graph with
+ * unit edge weights, by repeatedly augmenting along shortest paths.
+ * `neighbours` must be symmetric.
+ */
+pub fn min_cut<N, I>(source: N, sink: N, mut neighbours: impl FnMut(&N) -> I) -> Cut<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    // Net flow along each edge; an edge has spare capacity while this is < 1.
+    let mut flow: HashMap<(N, N), i32> = HashMap::new();
+    let mut size = 0;
+    loop {
+        let residual = |node: &N| -> Vec<N> {
+            neighbours(node)
+                .into_iter()
+                .filter(|next| {
+                    flow.get(&(node.clone(), next.clone()))
+                        .copied()
+                        .unwrap_or(0)
+                        < 1
+                })
+                .collect()
+        };
+        let Some(path) = bfs_path(source.clone(), residual, |node| *node == sink) else {
+            break;
+        };
+        for pair in path.windows(2) {
+            *flow.entry((pair[0].clone(), pair[1].clone())).or_default() += 1;
+            *flow.entry((pair[1].clone(), pair[0].clone())).or_default() -= 1;
+        }
+        size += 1;
+    }
+    let side = bfs(source, |node: &N| -> Vec<N> {
+        neighbours(node)
+            .into_iter()
+            .filter(|next| {
+                flow.get(&(node.clone(), next.clone()))
+                    .copied()
+                    .unwrap_or(0)
+                    < 1
+            })
+            .collect()
+    })
+    .map(|(node, _)| node)
+    .collect();
+    Cut { size, side }
+}
+
+/**
+ * Cost of the most expensive simple path from `start` to a node satisfying
+ * `is_goal`, by exhaustive depth-first search. Only practical for small
+ * graphs.
+ */
+pub fn longest_path<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    fn search<N, C, I>(
+        node: &N,
+        cost: C,
+        seen: &mut HashSet<N>,
+        neighbours: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        longest: &mut Option<C>,
+    ) where
+        N: Clone + Eq + Hash,
+        C: Copy + Ord + Add<Output = C>,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        if is_goal(node) {
+            *longest = (*longest).max(Some(cost));
+        }
+        for (next, step) in neighbours(node) {
+            if seen.insert(next.clone()) {
+                search(&next, cost + step, seen, neighbours, is_goal, longest);
+                seen.remove(&next);
+            }
+        }
+    }
+
+    let mut longest = None;
+    let mut seen = HashSet::from([start.clone()]);
+    search(
+        &start,
+        C::default(),
+        &mut seen,
+        &mut neighbours,
+        &mut is_goal,
+        &mut longest,
+    );
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two triangles, a-b-c and d-e-f, joined by the edge c-d.
+    static EDGES: [(char, char, u32); 7] = [
+        ('a', 'b', 1),
+        ('b', 'c', 1),
+        ('a', 'c', 5),
+        ('c', 'd', 2),
+        ('d', 'e', 1),
+        ('e', 'f', 1),
+        ('d', 'f', 3),
+    ];
+
+    fn weighted(node: &char) -> Vec<(char, u32)> {
+        EDGES
+            .iter()
+            .filter_map(|&(a, b, cost)| match node {
+                n if *n == a => Some((b, cost)),
+                n if *n == b => Some((a, cost)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn unweighted(node: &char) -> Vec<char> {
+        weighted(node).into_iter().map(|(next, _)| next).collect()
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(
+            dijkstra('a', weighted, |&n| n == 'f'),
+            Some(Path {
+                cost: 6,
+                nodes: vec!['a', 'b', 'c', 'd', 'e', 'f'],
+            })
+        );
+        assert_eq!(dijkstra('a', weighted, |&n| n == 'z'), None);
+        // An exact heuristic for the path to 'f' must not change the result.
+        let exact = |n: &char| dijkstra(*n, weighted, |&n| n == 'f').unwrap().cost;
+        assert_eq!(
+            astar('a', weighted, exact, |&n| n == 'f').map(|path| path.cost),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn test_bfs() {
+        let distances = bfs_distances('a', unweighted);
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[&'c'], 1);
+        assert_eq!(distances[&'f'], 3);
+        assert_eq!(
+            bfs_path('a', unweighted, |&n| n == 'e'),
+            Some(vec!['a', 'c', 'd', 'e'])
+        );
+        // Infinite graphs can be explored lazily.
+        let within: Vec<(i32, usize)> = bfs(0, |&n| [n - 1, n + 1])
+            .take_while(|&(_, distance)| distance <= 1)
+            .collect();
+        assert_eq!(within, vec![(0, 0), (-1, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let labels = connected_components("abcxy".chars(), |&n| match n {
+            'x' => vec!['y'],
+            'y' => vec!['x'],
+            n => unweighted(&n),
+        });
+        assert_eq!(labels[&'a'], 0);
+        assert_eq!(labels[&'f'], 0);
+        assert_eq!(labels[&'x'], 1);
+        assert_eq!(labels[&'y'], 1);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let cut = min_cut('a', 'f', unweighted);
+        assert_eq!(cut.size, 1);
+        assert_eq!(cut.side, HashSet::from(['a', 'b', 'c']));
+        assert_eq!(min_cut('a', 'b', unweighted).size, 2);
+    }
+
+    #[test]
+    fn test_longest_path() {
+        assert_eq!(longest_path('a', weighted, |&n| n == 'f'), Some(10));
+        assert_eq!(longest_path('a', weighted, |&n| n == 'z'), None::<u32>);
+    }
+}
@@ -0,0 +1,441 @@
+use common::parse_chars;
+use common::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/**
+ * Position of a cell, with `x` counting columns from the left and `y` rows
+ * from the top.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Coord { x, y }
+    }
+
+    /**
+     * Move by (dx, dy). Returns None if either coordinate would become
+     * negative; the far edges are checked by `Grid::contains`.
+     */
+    pub fn offset(self, (dx, dy): (isize, isize)) -> Option<Coord> {
+        Some(Coord {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+
+    pub fn step(self, direction: Direction) -> Option<Coord> {
+        self.offset(direction.delta())
+    }
+
+    pub fn manhattan(self, other: Coord) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Direction {
+    /**
+     * Every direction, clockwise from `Right`.
+     */
+    pub const ALL: [Direction; 4] = [
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+        Direction::Up,
+    ];
+
+    /**
+     * The change in (x, y) for one step.
+     */
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Up => (0, -1),
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Up => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::Up => Direction::Right,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.turn_right().reverse()
+    }
+}
+
+const DIAGONALS: [(isize, isize); 4] = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+
+/**
+ * A rectangular grid stored row by row in a single vector.
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /**
+     * Grid from cells in row order. Panics if there are not exactly
+     * `width * height` cells.
+     */
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid is not {width}x{height}");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /**
+     * Parse one cell per character, one row per line. Every row must be as
+     * wide as the first.
+     */
+    pub fn parse_with(
+        input: &str,
+        mut parse_cell: impl FnMut(char) -> Option<T>,
+        message: &str,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in input.lines() {
+            let row = parse_chars(line, &mut parse_cell, message)
+                .map_err(|err| err.within(input, line))?;
+            match width {
+                None => width = Some(row.len()),
+                Some(width) if width != row.len() => {
+                    return Err(ParseError::new(
+                        input,
+                        line,
+                        format!("expected {width} columns"),
+                    ));
+                }
+                Some(_) => {}
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        if self.contains(coord) {
+            Some(&self[coord])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        if self.contains(coord) {
+            Some(&mut self[coord])
+        } else {
+            None
+        }
+    }
+
+    /**
+     * Bounded lookup for signed positions, which may lie off any edge.
+     */
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        let coord = Coord::new(x.try_into().ok()?, y.try_into().ok()?);
+        self.get(coord)
+    }
+
+    /**
+     * Lookup treating the grid as tiled infinitely in every direction. None
+     * only if the grid is empty.
+     */
+    pub fn get_wrapping(&self, x: isize, y: isize) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        Some(&self[Coord::new(x, y)])
+    }
+
+    /**
+     * The neighbour of `coord` in `direction`, if it is inside the grid.
+     */
+    pub fn step(&self, coord: Coord, direction: Direction) -> Option<Coord> {
+        coord.step(direction).filter(|&next| self.contains(next))
+    }
+
+    /**
+     * Orthogonal neighbours inside the grid, clockwise from the right.
+     */
+    pub fn neighbours4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(coord, direction))
+    }
+
+    /**
+     * Orthogonal and diagonal neighbours inside the grid.
+     */
+    pub fn neighbours8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(Direction::delta)
+            .chain(DIAGONALS)
+            .filter_map(move |delta| coord.offset(delta))
+            .filter(|&next| self.contains(next))
+    }
+
+    /**
+     * Every position in row order.
+     */
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coord::new(x, y)))
+    }
+
+    /**
+     * Every cell with its position, in row order.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(coord, _)| coord)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn swap(&mut self, a: Coord, b: Coord) {
+        let a = self.index_of(a);
+        let b = self.index_of(b);
+        self.cells.swap(a, b);
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn index_of(&self, coord: Coord) -> usize {
+        assert!(
+            self.contains(coord),
+            "{coord:?} is outside the {}x{} grid",
+            self.width,
+            self.height
+        );
+        coord.y * self.width + coord.x
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /**
+     * Swap rows and columns.
+     */
+    pub fn transpose(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid::new(self.height, self.width, cells)
+    }
+
+    /**
+     * Rotate a quarter turn clockwise, so the left column becomes the top row.
+     */
+    pub fn rotate_clockwise(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).rev().cloned())
+            .collect();
+        Grid::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        &self.cells[self.index_of(coord)]
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        let index = self.index_of(coord);
+        &mut self.cells[index]
+    }
+}
+
+impl<T: Copy + Into<char>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for &cell in row {
+                write!(f, "{}", cell.into())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "abc
+def";
+
+    fn parse(input: &str) -> Grid<char> {
+        Grid::parse_with(input, Some, "invalid cell").unwrap()
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let grid = parse(SAMPLE);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord::new(2, 1)], 'f');
+        assert_eq!(grid.to_string(), SAMPLE);
+
+        let err = Grid::parse_with("01\n2x", |c| c.to_digit(10), "invalid digit").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+        let err = Grid::parse_with("01\n234", |c| c.to_digit(10), "invalid digit").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected 2 columns at \"234\""
+        );
+    }
+
+    #[test]
+    fn test_lookups() {
+        let grid = parse(SAMPLE);
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(1, 1), Some(&'e'));
+        assert_eq!(grid.get_wrapping(-1, 2), Some(&'c'));
+        assert_eq!(grid.get_wrapping(4, -1), Some(&'e'));
+        let empty: Grid<char> = Grid::parse_with("", Some, "invalid cell").unwrap();
+        assert_eq!(empty.get_wrapping(3, 0), None);
+        assert_eq!(grid.position(|&c| c == 'd'), Some(Coord::new(0, 1)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = parse(SAMPLE);
+        let corner: Vec<Coord> = grid.neighbours4(Coord::new(0, 0)).collect();
+        assert_eq!(corner, vec![Coord::new(1, 0), Coord::new(0, 1)]);
+        assert_eq!(grid.neighbours4(Coord::new(1, 1)).count(), 3);
+        assert_eq!(grid.neighbours8(Coord::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Coord::new(1, 1)).count(), 5);
+        assert_eq!(grid.step(Coord::new(2, 0), Direction::Right), None);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = parse(SAMPLE);
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, vec!["abc", "def"]);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = parse(SAMPLE);
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        let mut rotated = grid.clone();
+        for _ in 0..4 {
+            rotated = rotated.rotate_clockwise();
+        }
+        assert_eq!(rotated, grid);
+    }
+
+    #[test]
+    fn test_manhattan() {
+        assert_eq!(Coord::new(1, 6).manhattan(Coord::new(5, 11)), 9);
+        assert_eq!(Coord::new(4, 0).manhattan(Coord::new(9, 10)), 15);
+        assert_eq!(Coord::new(0, 11).manhattan(Coord::new(5, 11)), 5);
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Down.reverse(), Direction::Up);
+    }
+}